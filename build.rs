@@ -0,0 +1,54 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Collects every file below `dir`, sorted so the generated code is stable.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let mut entries = fs::read_dir(dir)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", dir.display(), e))
+        .map(|entry| entry.expect("failed to read directory entry").path())
+        .collect::<Vec<PathBuf>>();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Bundles every file in `resources/text`, so a language is added by dropping its files
+/// there. The generated `bundled_text` looks them up by their path below that directory.
+fn main() {
+    let root = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("resources/text");
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut files = vec![];
+    collect_files(&root, &mut files);
+
+    let mut code = String::from(
+        "/// Text file at `path` below `resources/text`, if one was bundled.\n\
+         pub(crate) fn bundled_text(path: &str) -> Option<&'static str> {\n    match path {\n",
+    );
+    for file in files {
+        let key = file
+            .strip_prefix(&root)
+            .unwrap()
+            .components()
+            .map(|component| component.as_os_str().to_str().expect("non UTF-8 file name"))
+            .collect::<Vec<&str>>()
+            .join("/");
+        let full_path = file.to_str().expect("non UTF-8 path");
+        writeln!(
+            code,
+            "        {:?} => Some(include_str!({:?})),",
+            key, full_path
+        )
+        .unwrap();
+    }
+    code.push_str("        _ => None,\n    }\n}\n");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("bundled_text.rs");
+    fs::write(out, code).expect("failed to write bundled_text.rs");
+}
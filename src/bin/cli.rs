@@ -0,0 +1,267 @@
+use bdsp_ug_generator::statues::get_statue_data;
+use bdsp_ug_generator::Version;
+use bdsp_ug_generator_ui::search::{self, ResultColumn, SearchRequest};
+use bdsp_ug_generator_ui::{
+    room_str, StoryFlag, HIDDEN_POWER_TYPES, ITEMS_EN, MOVES_EN, NATURES_EN, ROOMS, SPECIES_EN,
+    TYPES_EN,
+};
+
+const USAGE: &str =
+    "Usage: bdsp_ug_generator_cli --s0 <hex> --s1 <hex> --s2 <hex> --s3 <hex> [options]
+       bdsp_ug_generator_cli --state <state> [options]
+
+Search options:
+  --state <state>         Whole state instead of --s0 to --s3: 32 hex digits, four 32-bit
+                          words or two 64-bit halves, e.g. \"S[0]: 1234abcd ...\"
+  --min-advances <n>      First advance to search (default 0)
+  --max-advances <n>      Number of advances to search (default 10000)
+  --delay <n>             Delay added to the starting advance (default 0)
+  --version <bd|sp>       Game version (default bd)
+  --story-flag <1-6>      1 Underground Unlocked, 2 Strength Obtained, 3 Defog Obtained,
+                          4 7 Badges, 5 Waterfall Obtained, 6 National Dex (default 6)
+  --room <name>           Room name, e.g. \"Spacious Cave\" (default Spacious Cave)
+  --diglett               Enable diglett mode
+  --statue <name>         Add a statue, e.g. \"Pikachu\" or \"Pikachu - Rare\" (repeatable)
+
+Filter options:
+  --species <list>        Comma separated list of species names or ids (repeatable)
+  --shiny                 Only show shiny spawns
+  --gender <m|f|->        Only show this gender
+  --nature <list>         Comma separated list of natures
+  --ability <1|2>         Only show this ability slot
+  --item <name|id>        Only show this held item
+  --egg-move <name|id>    Only show this egg move
+  --min-ivs <a/b/c/d/e/f> Minimum IVs (default 0/0/0/0/0/0)
+  --max-ivs <a/b/c/d/e/f> Maximum IVs (default 31/31/31/31/31/31)
+  --min-perfect-ivs <n>   Minimum number of IVs at 31
+  --min-iv-total <n>      Minimum sum of all IVs (default 0)
+  --max-iv-total <n>      Maximum sum of all IVs (default 186)
+  --zero-atk              Only show spawns with 0 Atk IV
+  --zero-spe              Only show spawns with 0 Spe IV
+  --hidden-power <type>   Only show this hidden power type, e.g. \"Fire\"
+  --exclusive             Exclusive search
+  -h, --help              Print this message";
+
+#[derive(Default)]
+struct Args {
+    seeds: [Option<String>; 4],
+    request: SearchRequest,
+}
+
+fn find_name(list: &[&str], value: &str) -> Option<usize> {
+    if let Ok(index) = value.parse::<usize>() {
+        return if index < list.len() {
+            Some(index)
+        } else {
+            None
+        };
+    }
+    list.iter()
+        .position(|name| !name.is_empty() && name.eq_ignore_ascii_case(value))
+}
+
+fn parse_ivs(value: &str) -> Result<[u8; 6], String> {
+    let ivs = value
+        .split('/')
+        .map(|iv| match iv.trim().parse::<u8>() {
+            Ok(iv) if iv <= 31 => Ok(iv),
+            _ => Err(format!("Invalid IV \"{}\"", iv)),
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+    ivs.try_into()
+        .map_err(|_| format!("Expected 6 IVs separated by '/', got \"{}\"", value))
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(flag) = iter.next() {
+        match flag.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                std::process::exit(0);
+            }
+            "--diglett" => args.request.diglett_mode = true,
+            "--shiny" => args.request.filter.shiny = true,
+            "--exclusive" => args.request.filter.exclusive = true,
+            "--zero-atk" => args.request.filter.zero_atk = true,
+            "--zero-spe" => args.request.filter.zero_spe = true,
+            _ => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| format!("Missing value for {}", flag))?;
+                let parse_u32 = |v: &str| {
+                    v.parse::<u32>()
+                        .map_err(|_| format!("Invalid {} \"{}\"", flag, v))
+                };
+                match flag.as_str() {
+                    "--s0" => args.seeds[0] = Some(value),
+                    "--s1" => args.seeds[1] = Some(value),
+                    "--s2" => args.seeds[2] = Some(value),
+                    "--s3" => args.seeds[3] = Some(value),
+                    "--state" => {
+                        let state = search::split_state(&value)
+                            .ok_or_else(|| format!("Invalid state \"{}\"", value))?;
+                        args.seeds = state.map(|seed| Some(format!("{:08X}", seed)));
+                    }
+                    "--min-advances" => args.request.min_advances = parse_u32(&value)?,
+                    "--max-advances" => args.request.max_advances = parse_u32(&value)?,
+                    "--delay" => args.request.delay = parse_u32(&value)?,
+                    "--version" => {
+                        args.request.version = match value.to_ascii_lowercase().as_str() {
+                            "bd" | "brilliant diamond" => Version::BD,
+                            "sp" | "shining pearl" => Version::SP,
+                            _ => return Err(format!("Invalid version \"{}\"", value)),
+                        }
+                    }
+                    "--story-flag" => {
+                        args.request.story_flag = value
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|flag| StoryFlag::ALL.get(flag.wrapping_sub(1)))
+                            .copied()
+                            .ok_or_else(|| format!("Invalid story flag \"{}\"", value))?;
+                    }
+                    "--room" => {
+                        // The CLI never switches language, so room names are English.
+                        let name = value.replace(' ', "");
+                        args.request.room = ROOMS
+                            .into_iter()
+                            .find(|&room| {
+                                room_str(room).replace(' ', "").eq_ignore_ascii_case(&name)
+                            })
+                            .ok_or_else(|| format!("Invalid room \"{}\"", value))?;
+                    }
+                    "--statue" => {
+                        let statue = get_statue_data()
+                            .into_iter()
+                            .find(|s| {
+                                let name = if s.rarity == 1 {
+                                    SPECIES_EN[s.mons_id].to_string()
+                                } else {
+                                    format!("{} - Rare", SPECIES_EN[s.mons_id])
+                                };
+                                name.eq_ignore_ascii_case(&value)
+                            })
+                            .ok_or_else(|| format!("Invalid statue \"{}\"", value))?;
+                        args.request.statues.push(statue);
+                    }
+                    "--species" => {
+                        for species in value.split(',') {
+                            let species = find_name(&SPECIES_EN, species.trim())
+                                .ok_or_else(|| format!("Invalid species \"{}\"", species))?;
+                            args.request.filter.species.push(species as u16);
+                        }
+                    }
+                    "--gender" => {
+                        args.request.filter.gender = match value.to_ascii_lowercase().as_str() {
+                            "m" | "male" | "♂" => Some(0),
+                            "f" | "female" | "♀" => Some(1),
+                            "-" | "genderless" => Some(2),
+                            _ => return Err(format!("Invalid gender \"{}\"", value)),
+                        }
+                    }
+                    "--nature" => {
+                        let natures = value
+                            .split(',')
+                            .map(|nature| {
+                                find_name(&NATURES_EN, nature.trim())
+                                    .filter(|&i| i < 25)
+                                    .map(|i| i as u8)
+                                    .ok_or_else(|| format!("Invalid nature \"{}\"", nature))
+                            })
+                            .collect::<Result<Vec<u8>, String>>()?;
+                        args.request.filter.nature = Some(natures);
+                    }
+                    "--ability" => {
+                        args.request.filter.ability = match value.as_str() {
+                            "1" => Some(0),
+                            "2" => Some(1),
+                            _ => return Err(format!("Invalid ability slot \"{}\"", value)),
+                        }
+                    }
+                    "--item" => {
+                        let item = find_name(&ITEMS_EN, &value)
+                            .ok_or_else(|| format!("Invalid item \"{}\"", value))?;
+                        args.request.filter.item = Some(item as u16);
+                    }
+                    "--egg-move" => {
+                        let egg_move = find_name(&MOVES_EN, &value)
+                            .ok_or_else(|| format!("Invalid egg move \"{}\"", value))?;
+                        args.request.filter.egg_move = Some(egg_move as u16);
+                    }
+                    "--min-ivs" => args.request.filter.min_ivs = parse_ivs(&value)?,
+                    "--max-ivs" => args.request.filter.max_ivs = parse_ivs(&value)?,
+                    "--min-perfect-ivs" => match value.parse::<u8>() {
+                        Ok(count) if count <= 6 => args.request.filter.min_perfect_ivs = count,
+                        _ => return Err(format!("Invalid {} \"{}\"", flag, value)),
+                    },
+                    "--min-iv-total" | "--max-iv-total" => {
+                        let total = match value.parse::<u16>() {
+                            Ok(total) if total <= 186 => total,
+                            _ => return Err(format!("Invalid {} \"{}\"", flag, value)),
+                        };
+                        if flag == "--min-iv-total" {
+                            args.request.filter.min_iv_total = total;
+                        } else {
+                            args.request.filter.max_iv_total = total;
+                        }
+                    }
+                    "--hidden-power" => {
+                        let ty = HIDDEN_POWER_TYPES
+                            .into_iter()
+                            .find(|&ty| TYPES_EN[ty].eq_ignore_ascii_case(&value))
+                            .ok_or_else(|| format!("Invalid hidden power type \"{}\"", value))?;
+                        args.request.filter.hidden_power = Some(ty);
+                    }
+                    _ => return Err(format!("Unknown option {}", flag)),
+                }
+            }
+        }
+    }
+
+    Ok(args)
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let Args { seeds, mut request } = args;
+    let (s0, s1, s2, s3) = match &seeds {
+        [Some(s0), Some(s1), Some(s2), Some(s3)] => (s0, s1, s2, s3),
+        _ => {
+            eprintln!(
+                "Either --state or all of --s0, --s1, --s2 and --s3 are required\n\n{}",
+                USAGE
+            );
+            std::process::exit(2);
+        }
+    };
+
+    request.state = match search::parse_state(s0, s1, s2, s3) {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(e) = request.validate() {
+        eprintln!("{}", e);
+        std::process::exit(2);
+    }
+
+    let header = ResultColumn::ALL.map(ResultColumn::header);
+    println!("{}", header.join("\t"));
+    for row in search::search(&request).rows {
+        let fields = ResultColumn::ALL.map(|column| row.text(column));
+        println!("{}", fields.join("\t"));
+    }
+}
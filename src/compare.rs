@@ -0,0 +1,485 @@
+use crate::job::BatchJob;
+use crate::search::{ResultRow, SearchRequest};
+use crate::{room_str, text, version_str, BDSPUgGeneratorUI, StoryFlag, ROOMS};
+use bdsp_ug_generator::{available_pokemon, RoomType, Version};
+use eframe::egui;
+use std::collections::BTreeMap;
+
+/// Where a search first matched and how often.
+#[derive(Copy, Clone, Default)]
+pub struct Hits {
+    pub first_advance: Option<u32>,
+    /// Number of matching spawns, the rows the search shows.
+    pub matches: usize,
+    /// Number of advances with at least one matching spawn.
+    pub advances: usize,
+    last_advance: Option<u32>,
+}
+
+impl Hits {
+    /// Counts `rows`, which continue the search's earlier rows in advance order.
+    pub fn add(&mut self, rows: &[ResultRow]) {
+        for row in rows {
+            if self.last_advance != Some(row.advance) {
+                self.first_advance = self.first_advance.or(Some(row.advance));
+                self.advances += 1;
+                self.last_advance = Some(row.advance);
+            }
+        }
+        self.matches += rows.len();
+    }
+}
+
+/// `request` once in each of `rooms`, for a [`crate::job::BatchJob`].
+pub fn room_requests(request: &SearchRequest, rooms: &[RoomType]) -> Vec<SearchRequest> {
+    rooms
+        .iter()
+        .map(|&room| SearchRequest {
+            room,
+            ..request.clone()
+        })
+        .collect()
+}
+
+/// Puts the rooms with the soonest first match first.
+pub fn sort_room_hits(room_hits: &mut [(RoomType, Hits)]) {
+    // Stable, so rooms without a match stay in the order they were given.
+    room_hits.sort_by_key(|(_, hits)| hits.first_advance.unwrap_or(u32::MAX));
+}
+
+pub const VERSIONS: [Version; 2] = [Version::BD, Version::SP];
+
+/// `request` for every story flag in both versions, for a [`crate::job::BatchJob`]. Request
+/// `i` is for story flag `i / VERSIONS.len()` and version `i % VERSIONS.len()`.
+pub fn progress_requests(request: &SearchRequest) -> Vec<SearchRequest> {
+    StoryFlag::ALL
+        .into_iter()
+        .flat_map(|story_flag| {
+            VERSIONS.map(|version| SearchRequest {
+                version,
+                story_flag,
+                ..request.clone()
+            })
+        })
+        .collect()
+}
+
+/// Spawns matching the filter at one advance, with Diglett mode off and on.
+pub struct DiglettDiff {
+    pub advance: u32,
+    pub off: Vec<ResultRow>,
+    pub on: Vec<ResultRow>,
+}
+
+impl DiglettDiff {
+    pub fn differs(&self) -> bool {
+        self.off != self.on
+    }
+}
+
+/// `request` with Diglett mode off and then on, for a [`crate::job::BatchJob`].
+pub fn diglett_requests(request: &SearchRequest) -> Vec<SearchRequest> {
+    [false, true]
+        .into_iter()
+        .map(|diglett_mode| SearchRequest {
+            diglett_mode,
+            ..request.clone()
+        })
+        .collect()
+}
+
+/// Pairs up matches of the [`diglett_requests`] by advance. `request` is the index of the
+/// request `rows` came from.
+pub fn add_diglett_rows(
+    diffs: &mut BTreeMap<u32, DiglettDiff>,
+    request: usize,
+    rows: Vec<ResultRow>,
+) {
+    for row in rows {
+        let diff = diffs.entry(row.advance).or_insert_with(|| DiglettDiff {
+            advance: row.advance,
+            off: vec![],
+            on: vec![],
+        });
+        if request == 1 {
+            diff.on.push(row);
+        } else {
+            diff.off.push(row);
+        }
+    }
+}
+
+fn spawns_str(rows: &[ResultRow]) -> String {
+    if rows.is_empty() {
+        return "-".to_string();
+    }
+    rows.iter()
+        .map(|row| {
+            if row.shiny {
+                format!("{} ({})", row.species_str(), row.shiny_str())
+            } else {
+                row.species_str().to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn hits_str(hits: &Hits) -> String {
+    match hits.first_advance {
+        Some(advance) => format!("{} ({} / {})", advance, hits.matches, hits.advances),
+        None => "-".to_string(),
+    }
+}
+
+/// Cancel button and progress of a running comparison.
+fn show_batch_progress(ui: &mut egui::Ui, job: &mut BatchJob) {
+    if ui.button(text::ui("cancel")).clicked() {
+        job.cancel();
+    }
+    let (started, total) = job.searches();
+    ui.add(
+        egui::ProgressBar::new(job.progress())
+            .desired_width(150.0)
+            .text(text::ui_fmt("searches_progress", &[&started, &total])),
+    );
+}
+
+impl BDSPUgGeneratorUI {
+    pub(crate) fn show_room_compare_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(text::ui("compare_rooms"))
+            .id(egui::Id::new("room_compare_window"))
+            .show(ctx, |ui| {
+                ui.label(text::ui("compare_rooms_help"));
+                ui.add_space(5.0);
+
+                egui::Grid::new("room_compare_selection")
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for (i, (room, selected)) in
+                            ROOMS.iter().zip(&mut self.compared_rooms).enumerate()
+                        {
+                            ui.checkbox(selected, room_str(*room));
+                            if i % 3 == 2 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+
+                ui.horizontal(|ui| {
+                    if ui.button(text::ui("select_all")).clicked() {
+                        self.compared_rooms = [true; ROOMS.len()];
+                    }
+                    if ui.button(text::ui("select_none")).clicked() {
+                        self.compared_rooms = [false; ROOMS.len()];
+                    }
+                    if let Some(job) = self.room_job.as_mut() {
+                        show_batch_progress(ui, job);
+                    } else if ui
+                        .add_enabled(
+                            self.compared_rooms.contains(&true),
+                            egui::Button::new(text::ui("compare")),
+                        )
+                        .clicked()
+                    {
+                        match self.search_request() {
+                            Ok(request) => {
+                                let rooms = ROOMS
+                                    .iter()
+                                    .zip(self.compared_rooms)
+                                    .filter(|(_, selected)| *selected)
+                                    .map(|(room, _)| *room)
+                                    .collect::<Vec<RoomType>>();
+                                self.room_job =
+                                    Some(BatchJob::start(room_requests(&request, &rooms)));
+                                self.room_hits = Some(
+                                    rooms
+                                        .into_iter()
+                                        .map(|room| (room, Hits::default()))
+                                        .collect(),
+                                );
+                                self.error.clear();
+                            }
+                            Err(e) => self.error = e.to_string(),
+                        }
+                    }
+                    if ui.button(text::ui("close")).clicked() {
+                        self.show_room_compare = false;
+                        if let Some(job) = self.room_job.as_mut() {
+                            job.cancel();
+                        }
+                    }
+                });
+
+                if let Some(job) = self.room_job.as_mut() {
+                    let found = job.poll();
+                    if let Some(room_hits) = self.room_hits.as_mut() {
+                        for (index, rows) in found {
+                            room_hits[index].1.add(&rows);
+                        }
+                        if job.is_finished() {
+                            sort_room_hits(room_hits);
+                        }
+                    }
+                    if job.is_finished() {
+                        self.room_job = None;
+                    } else {
+                        ctx.request_repaint();
+                    }
+                }
+
+                if let Some(room_hits) = &self.room_hits {
+                    ui.add_space(5.0);
+                    let mut selected_room = None;
+                    egui::Grid::new("room_compare_results")
+                        .num_columns(5)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong(text::ui("room"));
+                            ui.strong(text::ui("first_advance"));
+                            ui.strong(text::ui("matching_spawns"));
+                            ui.strong(text::ui("matching_advances"));
+                            ui.end_row();
+                            for (room, hits) in room_hits {
+                                ui.label(room_str(*room));
+                                match hits.first_advance {
+                                    Some(advance) => ui.label(advance.to_string()),
+                                    None => ui.label("-"),
+                                };
+                                ui.label(hits.matches.to_string());
+                                ui.label(hits.advances.to_string());
+                                if ui.button(text::ui("use_room")).clicked() {
+                                    selected_room = Some(*room);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    if let Some(room) = selected_room {
+                        self.room = room;
+                        self.available_pokemon =
+                            available_pokemon(self.version, self.story_flag as u8, self.room);
+                    }
+                }
+            });
+    }
+
+    pub(crate) fn show_progress_compare_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(text::ui("compare_progress"))
+            .id(egui::Id::new("progress_compare_window"))
+            .show(ctx, |ui| {
+                ui.label(text::ui("compare_progress_help"));
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if let Some(job) = self.progress_job.as_mut() {
+                        show_batch_progress(ui, job);
+                    } else if ui.button(text::ui("compare")).clicked() {
+                        match self.search_request() {
+                            Ok(request) => {
+                                self.progress_job =
+                                    Some(BatchJob::start(progress_requests(&request)));
+                                self.progress_hits = Some(
+                                    StoryFlag::ALL
+                                        .into_iter()
+                                        .map(|story_flag| (story_flag, Default::default()))
+                                        .collect(),
+                                );
+                                self.error.clear();
+                            }
+                            Err(e) => self.error = e.to_string(),
+                        }
+                    }
+                    if ui.button(text::ui("close")).clicked() {
+                        self.show_progress_compare = false;
+                        if let Some(job) = self.progress_job.as_mut() {
+                            job.cancel();
+                        }
+                    }
+                });
+
+                if let Some(job) = self.progress_job.as_mut() {
+                    let found = job.poll();
+                    if let Some(progress_hits) = self.progress_hits.as_mut() {
+                        for (index, rows) in found {
+                            let (_, hits) = &mut progress_hits[index / VERSIONS.len()];
+                            hits[index % VERSIONS.len()].add(&rows);
+                        }
+                    }
+                    if job.is_finished() {
+                        self.progress_job = None;
+                    } else {
+                        ctx.request_repaint();
+                    }
+                }
+
+                if let Some(progress_hits) = &self.progress_hits {
+                    ui.add_space(5.0);
+                    let mut selected = None;
+                    egui::Grid::new("progress_compare_results")
+                        .num_columns(1 + VERSIONS.len())
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong(text::ui("story_flag"));
+                            for version in VERSIONS {
+                                ui.strong(version_str(version));
+                            }
+                            ui.end_row();
+                            for (story_flag, hits) in progress_hits {
+                                ui.label(story_flag.get_str());
+                                for (version, hits) in VERSIONS.into_iter().zip(hits) {
+                                    let current =
+                                        self.version == version && self.story_flag == *story_flag;
+                                    if ui
+                                        .selectable_label(current, hits_str(hits))
+                                        .on_hover_text(text::ui("compare_progress_hint"))
+                                        .clicked()
+                                    {
+                                        selected = Some((version, *story_flag));
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    if let Some((version, story_flag)) = selected {
+                        self.version = version;
+                        self.story_flag = story_flag;
+                        self.available_pokemon =
+                            available_pokemon(self.version, self.story_flag as u8, self.room);
+                    }
+                }
+            });
+    }
+
+    pub(crate) fn show_diglett_compare_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(text::ui("compare_diglett"))
+            .id(egui::Id::new("diglett_compare_window"))
+            .show(ctx, |ui| {
+                ui.label(text::ui("compare_diglett_help"));
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if let Some(job) = self.diglett_job.as_mut() {
+                        show_batch_progress(ui, job);
+                    } else if ui.button(text::ui("compare")).clicked() {
+                        match self.search_request() {
+                            Ok(request) => {
+                                self.diglett_job =
+                                    Some(BatchJob::start(diglett_requests(&request)));
+                                self.diglett_diffs = Some(BTreeMap::new());
+                                self.error.clear();
+                            }
+                            Err(e) => self.error = e.to_string(),
+                        }
+                    }
+                    ui.checkbox(&mut self.diglett_only_differing, text::ui("only_differing"));
+                    if ui.button(text::ui("close")).clicked() {
+                        self.show_diglett_compare = false;
+                        if let Some(job) = self.diglett_job.as_mut() {
+                            job.cancel();
+                        }
+                    }
+                });
+
+                if let Some(job) = self.diglett_job.as_mut() {
+                    let found = job.poll();
+                    if let Some(diffs) = self.diglett_diffs.as_mut() {
+                        for (index, rows) in found {
+                            add_diglett_rows(diffs, index, rows);
+                        }
+                    }
+                    if job.is_finished() {
+                        self.diglett_job = None;
+                    } else {
+                        ctx.request_repaint();
+                    }
+                }
+
+                if let Some(diffs) = &self.diglett_diffs {
+                    ui.add_space(5.0);
+                    let differing = diffs.values().filter(|diff| diff.differs()).count();
+                    ui.label(text::ui_fmt(
+                        "diglett_differing",
+                        &[&differing, &diffs.len()],
+                    ));
+                    egui::ScrollArea::vertical()
+                        .id_source("diglett_compare_results")
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("diglett_compare_grid")
+                                .num_columns(3)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.strong(text::ui("column_advance"));
+                                    ui.strong(text::ui("diglett_off"));
+                                    ui.strong(text::ui("diglett_on"));
+                                    ui.end_row();
+                                    for diff in diffs.values() {
+                                        let differs = diff.differs();
+                                        if self.diglett_only_differing && !differs {
+                                            continue;
+                                        }
+                                        let color = if differs {
+                                            egui::Color32::YELLOW
+                                        } else {
+                                            ui.visuals().text_color()
+                                        };
+                                        ui.colored_label(color, diff.advance.to_string());
+                                        ui.colored_label(color, spawns_str(&diff.off));
+                                        ui.colored_label(color, spawns_str(&diff.on));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(advance: u32) -> ResultRow {
+        ResultRow {
+            advance,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hits_count_spawns_and_advances_across_polls() {
+        let mut hits = Hits::default();
+        hits.add(&[row(3), row(3), row(5)]);
+        // An advance split across two polls is only counted once.
+        hits.add(&[row(5), row(8)]);
+        hits.add(&[]);
+        assert_eq!(hits.first_advance, Some(3));
+        assert_eq!(hits.matches, 5);
+        assert_eq!(hits.advances, 3);
+    }
+
+    #[test]
+    fn progress_requests_follow_the_grid_order() {
+        let requests = progress_requests(&SearchRequest::default());
+        assert_eq!(requests.len(), StoryFlag::ALL.len() * VERSIONS.len());
+        for (index, request) in requests.iter().enumerate() {
+            assert!(request.story_flag == StoryFlag::ALL[index / VERSIONS.len()]);
+            assert!(request.version == VERSIONS[index % VERSIONS.len()]);
+        }
+    }
+
+    #[test]
+    fn diglett_rows_pair_up_by_advance() {
+        let mut diffs = BTreeMap::new();
+        add_diglett_rows(&mut diffs, 0, vec![row(2), row(4)]);
+        add_diglett_rows(&mut diffs, 1, vec![row(4), row(7)]);
+        let diffs = diffs.into_values().collect::<Vec<DiglettDiff>>();
+        assert_eq!(
+            diffs.iter().map(|diff| diff.advance).collect::<Vec<u32>>(),
+            [2, 4, 7]
+        );
+        assert!(diffs[0].differs());
+        assert!(!diffs[1].differs());
+        assert!(diffs[2].differs());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::search::ResultRow;
+use crate::{text, BDSPUgGeneratorUI};
+use bdsp_ug_generator::personal_info_bdsp::PersonalInfoBDSP;
+use bdsp_ug_generator::personal_table;
+use eframe::egui;
+
+const STAT_IDS: [&str; 6] = [
+    "column_hp",
+    "column_atk",
+    "column_def",
+    "column_spa",
+    "column_spd",
+    "column_spe",
+];
+
+/// IV indices in the order natures and characteristics list stats.
+const GAME_STAT_ORDER: [usize; 6] = [0, 1, 2, 5, 3, 4];
+
+fn base_stats(personal_info: &PersonalInfoBDSP) -> [usize; 6] {
+    [
+        personal_info.get_hp(),
+        personal_info.get_atk(),
+        personal_info.get_def(),
+        personal_info.get_spa(),
+        personal_info.get_spd(),
+        personal_info.get_spe(),
+    ]
+}
+
+/// Stats at `level` with no EVs, in HP, Atk, Def, SpA, SpD, Spe order.
+fn stats(row: &ResultRow, base: [usize; 6], level: u8) -> [usize; 6] {
+    let level = level as usize;
+    // Natures raise the stat at nature / 5 and lower the one at nature % 5, skipping HP.
+    let raised = GAME_STAT_ORDER[1 + row.nature as usize / 5];
+    let lowered = GAME_STAT_ORDER[1 + row.nature as usize % 5];
+
+    let mut stats = [0; 6];
+    for (i, stat) in stats.iter_mut().enumerate() {
+        let value = (2 * base[i] + row.ivs[i] as usize) * level / 100;
+        *stat = if i == 0 {
+            value + level + 10
+        } else if raised == lowered {
+            value + 5
+        } else if i == raised {
+            (value + 5) * 11 / 10
+        } else if i == lowered {
+            (value + 5) * 9 / 10
+        } else {
+            value + 5
+        };
+    }
+    stats
+}
+
+/// The highest IV decides the characteristic. Ties go to the first stat found when starting
+/// at EC % 6 and wrapping around.
+fn characteristic(row: &ResultRow) -> &'static str {
+    let start = (row.ec % 6) as usize;
+    let max = row.ivs.iter().max().copied().unwrap_or_default();
+    let stat = (0..6)
+        .map(|i| (start + i) % 6)
+        .find(|&i| row.ivs[GAME_STAT_ORDER[i]] == max)
+        .unwrap_or_default();
+    text::characteristic(stat, max as usize % 5)
+}
+
+fn gender_ratio(personal_info: &PersonalInfoBDSP) -> String {
+    match personal_info.get_gender() {
+        255 => text::ui("genderless").to_string(),
+        254 => text::ui("female_only").to_string(),
+        0 => text::ui("male_only").to_string(),
+        ratio => {
+            let female = (ratio + 1) as f32 * 100.0 / 256.0;
+            text::ui_fmt(
+                "gender_ratio_split",
+                &[&format!("{:.1}", 100.0 - female), &format!("{:.1}", female)],
+            )
+        }
+    }
+}
+
+impl BDSPUgGeneratorUI {
+    pub(crate) fn show_details_panel(&mut self, ctx: &egui::Context) {
+        let row = match self.results_view.selected(&self.results) {
+            Some(row) => *row,
+            None => return,
+        };
+        let personal_info = personal_table::BDSP.get_form_entry(row.species as usize, 0);
+        let base = base_stats(personal_info);
+
+        egui::SidePanel::right("details_panel").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(row.species_str());
+                if ui.button(text::ui("set_as_target")).clicked() {
+                    self.tracker.set_target(row);
+                    self.show_tracker = true;
+                }
+                if ui.button(text::ui("close")).clicked() {
+                    self.results_view.clear_selection();
+                }
+            });
+
+            let type_1 = personal_info.get_type_1();
+            let type_2 = personal_info.get_type_2();
+            ui.label(if type_1 == type_2 {
+                text::type_name(type_1).to_string()
+            } else {
+                format!("{} / {}", text::type_name(type_1), text::type_name(type_2))
+            });
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.label(text::ui("level"));
+                ui.add(egui::DragValue::new(&mut self.details_level).clamp_range(1..=100))
+                    .on_hover_text(text::ui("level_hint"));
+            });
+            let stats = stats(&row, base, self.details_level);
+            egui::Grid::new("details_stats")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("");
+                    ui.strong(text::ui("base"));
+                    ui.strong(text::ui("iv"));
+                    ui.strong(text::ui("stat"));
+                    ui.end_row();
+                    for i in 0..6 {
+                        ui.label(text::ui(STAT_IDS[i]));
+                        ui.label(base[i].to_string());
+                        ui.label(row.ivs[i].to_string());
+                        ui.label(stats[i].to_string());
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(5.0);
+
+            egui::Grid::new("details_info")
+                .num_columns(2)
+                .spacing([10.0, 4.0])
+                .show(ui, |ui| {
+                    let mut line = |id: &'static str, value: String| {
+                        ui.label(text::ui(id));
+                        ui.label(value);
+                        ui.end_row();
+                    };
+                    line("advance", row.advance.to_string());
+                    line("nature", row.nature_str().to_string());
+                    line("hidden_power", row.hidden_power_str().to_string());
+                    line("characteristic", characteristic(&row).to_string());
+                    line(
+                        "ability",
+                        format!("{} ({})", row.ability_str(), row.ability + 1),
+                    );
+                    line("abilities", {
+                        let ability_1 = personal_info.get_ability_1();
+                        let ability_2 = personal_info.get_ability_2();
+                        if ability_1 == ability_2 {
+                            text::ability(ability_1).to_string()
+                        } else {
+                            format!(
+                                "{} / {}",
+                                text::ability(ability_1),
+                                text::ability(ability_2)
+                            )
+                        }
+                    });
+                    line("gender", row.gender_str().to_string());
+                    line("gender_ratio", gender_ratio(personal_info));
+                    line("item", row.item_str().to_string());
+                    line("egg_move", row.egg_move_str().to_string());
+                    line("column_pid", row.pid_str());
+                    line("column_ec", row.ec_str());
+                    line(
+                        "shiny",
+                        text::ui(if row.shiny { "yes" } else { "no" }).to_string(),
+                    );
+                    line(
+                        "rare",
+                        text::ui(if row.rare { "yes" } else { "no" }).to_string(),
+                    );
+                });
+        });
+    }
+}
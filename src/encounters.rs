@@ -0,0 +1,291 @@
+use crate::search::{search, ResultRow, SearchFilter, SearchRequest};
+use crate::{text, BDSPUgGeneratorUI};
+use bdsp_ug_generator::available_pokemon;
+use eframe::egui;
+use std::cmp::Ordering;
+
+/// Number of independent states sampled to estimate spawn rates.
+pub const SAMPLE_SIZE: u32 = 2000;
+
+/// Seed the sampled states are drawn from, fixed so rates for different layouts are comparable.
+const SAMPLE_SEED: u64 = 0x1234_5678_9ABC_DEF0;
+
+/// How often a species appeared in the sample.
+pub struct EncounterRate {
+    pub species: u16,
+    /// Share of regular spawns.
+    pub rate: Estimate,
+    /// Share of rare spawns. A species missing from the sample's rare spawns may still be
+    /// able to spawn rare.
+    pub rare_rate: Estimate,
+}
+
+/// [`SAMPLE_SIZE`] states spread over the whole state space with SplitMix64.
+fn sample_states() -> impl Iterator<Item = [u32; 4]> {
+    let mut seed = SAMPLE_SEED;
+    let mut next = move || {
+        seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    std::iter::repeat_with(move || {
+        let (a, b) = (next(), next());
+        [a as u32, (a >> 32) as u32, b as u32, (b >> 32) as u32]
+    })
+    .take(SAMPLE_SIZE as usize)
+}
+
+/// Generates the spawns of one advance from each of the sampled states, with the request's
+/// version, story flag, room, Diglett mode and statues and no filter.
+///
+/// The generator doesn't expose spawn weights or which species can spawn rare, so rates are
+/// measured from this sample. Consecutive advances share most of their random numbers, so
+/// each sample starts from its own state rather than the next advance of the last one.
+fn sample(request: &SearchRequest) -> Vec<Vec<ResultRow>> {
+    sample_states()
+        .map(|state| {
+            let request = SearchRequest {
+                state,
+                min_advances: 0,
+                max_advances: 0,
+                delay: 0,
+                filter: SearchFilter::default(),
+                ..request.clone()
+            };
+            search(&request).rows
+        })
+        .collect()
+}
+
+/// A rate measured from a sample, with its standard error.
+#[derive(Copy, Clone, Default)]
+pub struct Estimate {
+    pub rate: f32,
+    pub error: f32,
+}
+
+impl Estimate {
+    /// Share of matching spawns over all samples, given `(matching, spawns)` per sample.
+    ///
+    /// The spawns of one sample come from the same random numbers and aren't independent,
+    /// so the error is worked out from how much each sample strays from the overall share
+    /// rather than treating every spawn as its own draw.
+    fn from_samples(samples: &[(u32, u32)]) -> Self {
+        let matching = samples.iter().map(|&(matching, _)| matching).sum::<u32>();
+        let spawns = samples.iter().map(|&(_, spawns)| spawns).sum::<u32>();
+        if spawns == 0 {
+            return Self::default();
+        }
+        let rate = matching as f32 / spawns as f32;
+        let count = samples.len() as f32;
+        let squares = samples
+            .iter()
+            .map(|&(matching, spawns)| (matching as f32 - rate * spawns as f32).powi(2))
+            .sum::<f32>();
+        let error = if samples.len() > 1 {
+            (count / (count - 1.0) * squares).sqrt() / spawns as f32
+        } else {
+            0.0
+        };
+        Self { rate, error }
+    }
+}
+
+/// `(matching, spawns)` of one species for each sampled state.
+///
+/// Every request is sampled on the same states, so two layouts can be compared state by
+/// state instead of as two separate samples.
+#[derive(Clone, Default)]
+pub struct SpawnCounts(Vec<(u32, u32)>);
+
+impl SpawnCounts {
+    pub fn estimate(&self) -> Estimate {
+        Estimate::from_samples(&self.0)
+    }
+
+    /// Whether `other` is higher by more than two standard errors of the difference.
+    ///
+    /// Both layouts see the same random numbers on each state, so most of their spread is
+    /// shared and cancels out of the difference. Combining their separate errors would
+    /// count it twice.
+    pub fn significantly_below(&self, other: &SpawnCounts) -> bool {
+        let (mine, theirs) = (self.estimate(), other.estimate());
+        let spawns =
+            |counts: &SpawnCounts| counts.0.iter().map(|&(_, spawns)| spawns).sum::<u32>() as f32;
+        let (my_spawns, their_spawns) = (spawns(self), spawns(other));
+        if self.0.len() != other.0.len()
+            || self.0.len() < 2
+            || my_spawns == 0.0
+            || their_spawns == 0.0
+        {
+            return false;
+        }
+        let residual = |(matching, spawns): (u32, u32), estimate: &Estimate, total: f32| {
+            (matching as f32 - estimate.rate * spawns as f32) / total
+        };
+        let squares = self
+            .0
+            .iter()
+            .zip(&other.0)
+            .map(|(&mine_state, &their_state)| {
+                (residual(their_state, &theirs, their_spawns)
+                    - residual(mine_state, &mine, my_spawns))
+                .powi(2)
+            })
+            .sum::<f32>();
+        let count = self.0.len() as f32;
+        let error = (count / (count - 1.0) * squares).sqrt();
+        theirs.rate - mine.rate > 2.0 * error
+    }
+}
+
+/// `(matching, spawns)` per sample, counting the spawns `counted` accepts and the ones of
+/// `species` among them.
+fn counts(
+    samples: &[Vec<ResultRow>],
+    species: u16,
+    counted: impl Fn(&ResultRow) -> bool,
+) -> Vec<(u32, u32)> {
+    samples
+        .iter()
+        .map(|rows| {
+            rows.iter()
+                .filter(|row| counted(row))
+                .fold((0, 0), |(matching, spawns), row| {
+                    (matching + (row.species == species) as u32, spawns + 1)
+                })
+        })
+        .collect()
+}
+
+/// Counts the regular spawns of `species` on every sampled state.
+pub fn spawn_counts(request: &SearchRequest, species: u16) -> SpawnCounts {
+    SpawnCounts(counts(&sample(request), species, |row| !row.rare))
+}
+
+/// Estimates regular and rare spawn rates for every species available in the request's room,
+/// most common first.
+pub fn spawn_rates(request: &SearchRequest) -> Vec<EncounterRate> {
+    let samples = sample(request);
+    let mut rates = available_pokemon(request.version, request.story_flag as u8, request.room)
+        .into_iter()
+        .map(|species| EncounterRate {
+            species,
+            rate: Estimate::from_samples(&counts(&samples, species, |row| !row.rare)),
+            rare_rate: Estimate::from_samples(&counts(&samples, species, |row| row.rare)),
+        })
+        .collect::<Vec<EncounterRate>>();
+    rates.sort_by(|a, b| {
+        (b.rate.rate, b.rare_rate.rate)
+            .partial_cmp(&(a.rate.rate, a.rare_rate.rate))
+            .unwrap_or(Ordering::Equal)
+    });
+    rates
+}
+
+fn estimate_str(estimate: &Estimate) -> String {
+    format!(
+        "{:.1}% ± {:.1}",
+        estimate.rate * 100.0,
+        estimate.error * 100.0
+    )
+}
+
+impl BDSPUgGeneratorUI {
+    pub(crate) fn show_encounter_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(text::ui("encounters"))
+            .id(egui::Id::new("encounter_window"))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(text::ui("calculate"))
+                        .on_hover_text(text::ui_fmt("calculate_hint", &[&SAMPLE_SIZE]))
+                        .clicked()
+                    {
+                        let request = SearchRequest {
+                            version: self.version,
+                            story_flag: self.story_flag,
+                            room: self.room,
+                            diglett_mode: self.diglett_mode,
+                            statues: self.statue_config.statues.clone(),
+                            ..Default::default()
+                        };
+                        self.encounter_rates = Some(spawn_rates(&request));
+                    }
+
+                    if ui.button(text::ui("close")).clicked() {
+                        self.show_encounters = false;
+                    }
+                });
+
+                if let Some(rates) = &self.encounter_rates {
+                    ui.label(text::ui_fmt("sampled_rates_note", &[&SAMPLE_SIZE]));
+                    egui::ScrollArea::vertical()
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("encounter_grid")
+                                .num_columns(3)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.strong(text::ui("species"));
+                                    ui.strong(text::ui("sampled_rate"));
+                                    ui.strong(text::ui("sampled_rare_rate"));
+                                    ui.end_row();
+                                    for rate in rates {
+                                        ui.label(text::species(rate.species as usize));
+                                        ui.label(estimate_str(&rate.rate));
+                                        if rate.rare_rate.rate > 0.0 {
+                                            ui.label(estimate_str(&rate.rare_rate));
+                                        } else {
+                                            ui.label(text::ui("not_in_sample"))
+                                                .on_hover_text(text::ui("not_in_sample_hint"));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_grows_with_correlated_samples() {
+        // Same share of 1/4 either way, but the second sample has it all-or-nothing per state.
+        let spread = Estimate::from_samples(&[(1, 4), (1, 4), (1, 4), (1, 4)]);
+        let clumped = Estimate::from_samples(&[(4, 4), (0, 4), (0, 4), (0, 4)]);
+        assert_eq!(spread.rate, 0.25);
+        assert_eq!(clumped.rate, 0.25);
+        assert_eq!(spread.error, 0.0);
+        assert!(clumped.error > 0.2);
+    }
+
+    #[test]
+    fn sample_states_are_distinct() {
+        let states = sample_states().collect::<Vec<[u32; 4]>>();
+        assert_eq!(states.len(), SAMPLE_SIZE as usize);
+        let mut sorted = states.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), states.len());
+    }
+
+    #[test]
+    fn shared_spread_cancels_out_of_the_difference() {
+        // Both layouts swing together from state to state, the second one spawning one more
+        // of the species on every state.
+        let low = SpawnCounts(vec![(0, 10), (5, 10), (1, 10), (6, 10)]);
+        let high = SpawnCounts(vec![(1, 10), (6, 10), (2, 10), (7, 10)]);
+        let (low_rate, high_rate) = (low.estimate(), high.estimate());
+        assert!(high_rate.rate - low_rate.rate < low_rate.error.hypot(high_rate.error));
+        assert!(low.significantly_below(&high));
+        assert!(!high.significantly_below(&low));
+        assert!(!low.significantly_below(&low));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::search::ResultRow;
+use crate::text;
+use serde::Serialize;
+
+const CSV_HEADER: [&str; 23] = [
+    "Advance",
+    "PID",
+    "Species",
+    "Shiny",
+    "HP",
+    "ATK",
+    "DEF",
+    "SPA",
+    "SPD",
+    "SPE",
+    "Ability",
+    "Gender",
+    "Nature",
+    "Item",
+    "Egg Move",
+    "EC",
+    "Species ID",
+    "Nature ID",
+    "Ability Slot",
+    "Ability ID",
+    "Item ID",
+    "Egg Move ID",
+    "Rare",
+];
+
+/// A result with both the displayed text and the raw ids behind it.
+#[derive(Serialize)]
+struct ExportRow {
+    advance: u32,
+    pid: String,
+    species: &'static str,
+    shiny: &'static str,
+    hp: u8,
+    atk: u8,
+    def: u8,
+    spa: u8,
+    spd: u8,
+    spe: u8,
+    ability: &'static str,
+    gender: &'static str,
+    nature: &'static str,
+    item: &'static str,
+    egg_move: &'static str,
+    ec: String,
+    species_id: u16,
+    nature_id: u8,
+    ability_slot: u8,
+    ability_id: usize,
+    item_id: u16,
+    egg_move_id: Option<u16>,
+    rare: bool,
+}
+
+impl From<&ResultRow> for ExportRow {
+    fn from(row: &ResultRow) -> Self {
+        Self {
+            advance: row.advance,
+            pid: row.pid_str(),
+            species: row.species_str(),
+            shiny: row.shiny_str(),
+            hp: row.ivs[0],
+            atk: row.ivs[1],
+            def: row.ivs[2],
+            spa: row.ivs[3],
+            spd: row.ivs[4],
+            spe: row.ivs[5],
+            ability: row.ability_str(),
+            gender: row.gender_str(),
+            nature: row.nature_str(),
+            item: row.item_str(),
+            egg_move: row.egg_move_str(),
+            ec: row.ec_str(),
+            species_id: row.species,
+            nature_id: row.nature,
+            ability_slot: row.ability,
+            ability_id: row.ability_id(),
+            item_id: row.item,
+            egg_move_id: row.egg_move,
+            rare: row.rare,
+        }
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn to_csv(rows: &[ResultRow]) -> String {
+    let mut csv = CSV_HEADER.join(",");
+    csv.push_str("\r\n");
+    for row in rows {
+        let row = ExportRow::from(row);
+        let fields = [
+            row.advance.to_string(),
+            row.pid,
+            csv_field(row.species),
+            row.shiny.to_string(),
+            row.hp.to_string(),
+            row.atk.to_string(),
+            row.def.to_string(),
+            row.spa.to_string(),
+            row.spd.to_string(),
+            row.spe.to_string(),
+            csv_field(row.ability),
+            row.gender.to_string(),
+            csv_field(row.nature),
+            csv_field(row.item),
+            csv_field(row.egg_move),
+            row.ec,
+            row.species_id.to_string(),
+            row.nature_id.to_string(),
+            row.ability_slot.to_string(),
+            row.ability_id.to_string(),
+            row.item_id.to_string(),
+            row.egg_move_id.map(|id| id.to_string()).unwrap_or_default(),
+            row.rare.to_string(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+pub fn to_json(rows: &[ResultRow]) -> String {
+    let rows = rows.iter().map(ExportRow::from).collect::<Vec<_>>();
+    serde_json::to_string_pretty(&rows).unwrap_or_default()
+}
+
+/// Asks the user where to save `contents`. Returns `Ok(false)` if the dialog was dismissed.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(file_name: &str, extension: &str, contents: &str) -> Result<bool, String> {
+    let path = rfd::FileDialog::new()
+        .add_filter(&extension.to_uppercase(), &[extension])
+        .set_file_name(file_name)
+        .save_file();
+    match path {
+        Some(path) => std::fs::write(path, contents)
+            .map(|_| true)
+            .map_err(|e| text::ui_fmt("save_failed", &[&e])),
+        None => Ok(false),
+    }
+}
+
+/// Starts a browser download of `contents`.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(file_name: &str, extension: &str, contents: &str) -> Result<bool, String> {
+    use eframe::wasm_bindgen::{JsCast, JsValue};
+
+    let error = |_| text::ui("download_failed").to_string();
+    let mime = match extension {
+        "json" => "application/json",
+        _ => "text/csv",
+    };
+
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_(mime);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options).map_err(error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(error)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| text::ui("download_failed").to_string())?;
+    let anchor = document
+        .create_element("a")
+        .map_err(error)?
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .map_err(|_| text::ui("download_failed").to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url).map_err(error)?;
+    Ok(true)
+}
@@ -0,0 +1,288 @@
+use crate::job::SearchJob;
+use crate::search::{self, ResultRow, SearchFilter, SearchRequest};
+use crate::{text, BDSPUgGeneratorUI, GENDER_SYMBOLS};
+use eframe::egui;
+
+/// A spawn seen in game. Unset fields match anything.
+#[derive(Clone, Default)]
+pub struct Observation {
+    pub species: Option<u16>,
+    pub nature: Option<u8>,
+    pub gender: Option<u8>,
+    pub ivs: [Option<u8>; 6],
+}
+
+impl Observation {
+    fn matches(&self, row: &ResultRow) -> bool {
+        self.species.unwrap_or(row.species) == row.species
+            && self.nature.unwrap_or(row.nature) == row.nature
+            && self.gender.unwrap_or(row.gender) == row.gender
+            && self
+                .ivs
+                .iter()
+                .zip(row.ivs)
+                .all(|(iv, actual)| iv.unwrap_or(actual) == actual)
+    }
+}
+
+/// Whether each observation can be paired with a different spawn.
+fn assign(observations: &[Observation], spawns: &[&ResultRow], used: &mut [bool]) -> bool {
+    let (observation, rest) = match observations.split_first() {
+        Some(split) => split,
+        None => return true,
+    };
+    for (i, spawn) in spawns.iter().enumerate() {
+        if !used[i] && observation.matches(spawn) {
+            used[i] = true;
+            if assign(rest, spawns, used) {
+                return true;
+            }
+            used[i] = false;
+        }
+    }
+    false
+}
+
+/// The request with its filter removed, so every spawn can be compared to the observations.
+pub fn finder_request(request: &SearchRequest) -> SearchRequest {
+    SearchRequest {
+        filter: SearchFilter::default(),
+        ..request.clone()
+    }
+}
+
+/// Searches the request's advance range for advances whose room holds every observed spawn.
+///
+/// Only the regular spawns of the room are compared, and the request's filter is ignored.
+pub fn find_advances(request: &SearchRequest, observations: &[Observation]) -> Vec<u32> {
+    let rows = search::search(&finder_request(request)).rows;
+    matching_advances(&rows, observations)
+}
+
+/// Advances among `rows` whose room holds every observed spawn. `rows` must hold every
+/// spawn of each advance they include, as a whole search or one [`SearchJob`] poll does.
+pub fn matching_advances(rows: &[ResultRow], observations: &[Observation]) -> Vec<u32> {
+    let mut advances = vec![];
+    let mut start = 0;
+    while start < rows.len() {
+        let advance = rows[start].advance;
+        let end = start
+            + rows[start..]
+                .iter()
+                .take_while(|row| row.advance == advance)
+                .count();
+        let spawns = rows[start..end]
+            .iter()
+            .filter(|row| !row.rare)
+            .collect::<Vec<&ResultRow>>();
+        if assign(observations, &spawns, &mut vec![false; spawns.len()]) {
+            advances.push(advance);
+        }
+        start = end;
+    }
+    advances
+}
+
+impl BDSPUgGeneratorUI {
+    pub(crate) fn show_finder_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(text::ui("find_my_state"))
+            .id(egui::Id::new("finder_window"))
+            .show(ctx, |ui| {
+                ui.label(text::ui("finder_help"));
+                ui.label(text::ui("finder_brute_force"));
+                ui.add_space(5.0);
+
+                let mut remove = None;
+                egui::Grid::new("finder_observations")
+                    .num_columns(9)
+                    .spacing([5.0, 5.0])
+                    .show(ui, |ui| {
+                        for id in [
+                            "column_species",
+                            "column_nature",
+                            "column_gender",
+                            "column_hp",
+                            "column_atk",
+                            "column_def",
+                            "column_spa",
+                            "column_spd",
+                            "column_spe",
+                        ] {
+                            ui.strong(text::ui(id));
+                        }
+                        ui.end_row();
+
+                        for (index, (observation, ivs)) in self
+                            .observations
+                            .iter_mut()
+                            .zip(&mut self.observation_ivs)
+                            .enumerate()
+                        {
+                            egui::ComboBox::from_id_source(("finder_species", index))
+                                .width(110.0)
+                                .selected_text(match observation.species {
+                                    Some(species) => text::species(species as usize),
+                                    None => text::ui("any"),
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut observation.species,
+                                        None,
+                                        text::ui("any"),
+                                    );
+                                    for &species in &self.available_pokemon {
+                                        ui.selectable_value(
+                                            &mut observation.species,
+                                            Some(species),
+                                            text::species(species as usize),
+                                        );
+                                    }
+                                });
+                            egui::ComboBox::from_id_source(("finder_nature", index))
+                                .width(80.0)
+                                .selected_text(match observation.nature {
+                                    Some(nature) => text::nature(nature as usize),
+                                    None => text::ui("any"),
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut observation.nature,
+                                        None,
+                                        text::ui("any"),
+                                    );
+                                    for nature in 0..25 {
+                                        ui.selectable_value(
+                                            &mut observation.nature,
+                                            Some(nature),
+                                            text::nature(nature as usize),
+                                        );
+                                    }
+                                });
+                            egui::ComboBox::from_id_source(("finder_gender", index))
+                                .width(40.0)
+                                .selected_text(match observation.gender {
+                                    Some(gender) => GENDER_SYMBOLS[gender as usize],
+                                    None => text::ui("any"),
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut observation.gender,
+                                        None,
+                                        text::ui("any"),
+                                    );
+                                    for (gender, symbol) in GENDER_SYMBOLS.iter().enumerate() {
+                                        ui.selectable_value(
+                                            &mut observation.gender,
+                                            Some(gender as u8),
+                                            *symbol,
+                                        );
+                                    }
+                                });
+                            for (iv, text) in observation.ivs.iter_mut().zip(ivs.iter_mut()) {
+                                let valid = text.is_empty() || iv.is_some();
+                                let mut edit = egui::TextEdit::singleline(text)
+                                    .desired_width(25.0)
+                                    .hint_text("?");
+                                if !valid {
+                                    edit = edit.text_color(egui::Color32::RED);
+                                }
+                                if ui.add(edit).changed() {
+                                    *iv = text.trim().parse::<u8>().ok().filter(|&iv| iv <= 31);
+                                }
+                            }
+                            if ui.button(text::ui("remove")).clicked() {
+                                remove = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(index) = remove {
+                    self.observations.remove(index);
+                    self.observation_ivs.remove(index);
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button(text::ui("add_spawn")).clicked() {
+                        self.observations.push(Observation::default());
+                        self.observation_ivs.push(Default::default());
+                    }
+                    if let Some(job) = self.finder_job.as_mut() {
+                        if ui.button(text::ui("cancel")).clicked() {
+                            job.cancel();
+                        }
+                        ui.add(
+                            egui::ProgressBar::new(job.progress())
+                                .desired_width(150.0)
+                                .text(format!("{} / {}", job.searched(), job.total())),
+                        );
+                    } else if ui
+                        .add_enabled(
+                            !self.observations.is_empty(),
+                            egui::Button::new(text::ui("find")),
+                        )
+                        .clicked()
+                    {
+                        match self.unfiltered_request() {
+                            Ok(request) => {
+                                self.finder_job = Some(SearchJob::start(request));
+                                self.found_advances = Some(vec![]);
+                                self.error.clear();
+                            }
+                            Err(e) => self.error = e.to_string(),
+                        }
+                    }
+                    if ui.button(text::ui("close")).clicked() {
+                        self.show_finder = false;
+                        if let Some(job) = self.finder_job.as_mut() {
+                            job.cancel();
+                        }
+                    }
+                });
+
+                // Each poll holds whole advances, so they can be matched as they arrive.
+                if let Some(job) = self.finder_job.as_mut() {
+                    let rows = job.poll();
+                    if let Some(advances) = self.found_advances.as_mut() {
+                        advances.extend(matching_advances(&rows, &self.observations));
+                    }
+                    if job.is_finished() {
+                        self.finder_job = None;
+                    } else {
+                        ctx.request_repaint();
+                    }
+                }
+
+                if let Some(advances) = &self.found_advances {
+                    ui.add_space(5.0);
+                    if advances.is_empty() {
+                        ui.label(text::ui("finder_no_match"));
+                    } else {
+                        ui.label(text::ui_fmt("finder_matches", &[&advances.len()]));
+                    }
+                    let mut track = None;
+                    egui::ScrollArea::vertical()
+                        .id_source("finder_results")
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for &advance in advances {
+                                ui.horizontal(|ui| {
+                                    ui.label(text::ui_fmt("advance_n", &[&advance]));
+                                    if ui.button(text::ui("track")).clicked() {
+                                        track = Some(advance);
+                                    }
+                                });
+                            }
+                        });
+                    if let Some(advance) = track {
+                        if let Ok(state) =
+                            search::parse_state(&self.s0, &self.s1, &self.s2, &self.s3)
+                        {
+                            self.tracker.reset(state);
+                            self.tracker.advance_to(advance);
+                            self.show_tracker = true;
+                        }
+                    }
+                }
+            });
+    }
+}
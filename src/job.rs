@@ -0,0 +1,362 @@
+use crate::search::{search_range, ResultRow, SearchRequest};
+
+/// Number of advances searched between progress updates.
+pub const CHUNK_SIZE: u32 = 2000;
+
+/// Work a [`Job`] runs one step at a time.
+pub trait Steps: Send + 'static {
+    type Output: Send + 'static;
+
+    /// Runs one step. Returns its output and whether it was the last step.
+    fn step(&mut self) -> (Self::Output, bool);
+
+    /// Amount of work done so far, in whatever unit the progress is measured in.
+    fn completed(&self) -> u32;
+}
+
+/// Work running outside the UI update.
+///
+/// Natively the steps run on a background thread. On wasm32 there are no threads, so each
+/// call to [`Job::poll`] runs one step before returning. Either way, `poll` hands back the
+/// outputs of the steps finished since the previous call.
+pub struct Job<S: Steps> {
+    #[cfg(not(target_arch = "wasm32"))]
+    inner: native::Job<S::Output>,
+    #[cfg(target_arch = "wasm32")]
+    inner: web::Job<S>,
+}
+
+impl<S: Steps> Job<S> {
+    pub fn start(steps: S) -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            inner: native::Job::start(steps),
+            #[cfg(target_arch = "wasm32")]
+            inner: web::Job::start(steps),
+        }
+    }
+
+    /// Returns the outputs of the steps finished since the last call.
+    pub fn poll(&mut self) -> Vec<S::Output> {
+        self.inner.poll()
+    }
+
+    pub fn completed(&self) -> u32 {
+        self.inner.completed()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    /// Stops the work after the step currently running.
+    pub fn cancel(&mut self) {
+        self.inner.cancel();
+    }
+}
+
+/// A search running as a [`Job`], one chunk of advances per step.
+pub struct SearchJob {
+    total: u32,
+    job: Job<Chunks>,
+}
+
+impl SearchJob {
+    pub fn start(request: SearchRequest) -> Self {
+        Self {
+            total: request.max_advances,
+            job: Job::start(Chunks {
+                request,
+                searched: 0,
+            }),
+        }
+    }
+
+    /// Returns the rows found since the last call.
+    pub fn poll(&mut self) -> Vec<ResultRow> {
+        self.job.poll().into_iter().flatten().collect()
+    }
+
+    /// Fraction of the advance range searched so far.
+    pub fn progress(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.searched() as f32 / self.total as f32
+        }
+    }
+
+    pub fn searched(&self) -> u32 {
+        self.job.completed()
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.job.is_finished()
+    }
+
+    /// Stops the search after the chunk currently being searched.
+    pub fn cancel(&mut self) {
+        self.job.cancel();
+    }
+}
+
+/// The advance range of a search, split into chunks of [`CHUNK_SIZE`].
+struct Chunks {
+    request: SearchRequest,
+    searched: u32,
+}
+
+impl Steps for Chunks {
+    type Output = Vec<ResultRow>;
+
+    fn step(&mut self) -> (Vec<ResultRow>, bool) {
+        let (len, last) = next_chunk(self.searched, self.request.max_advances);
+        let rows = search_range(&self.request, self.searched, len, last);
+        self.searched += len;
+        (rows, last)
+    }
+
+    fn completed(&self) -> u32 {
+        self.searched
+    }
+}
+
+/// Several searches run one after another, each as a [`SearchJob`].
+pub struct BatchJob {
+    requests: Vec<SearchRequest>,
+    /// Index of the request being searched and its job.
+    current: Option<(usize, SearchJob)>,
+    cancelled: bool,
+}
+
+impl BatchJob {
+    pub fn start(requests: Vec<SearchRequest>) -> Self {
+        let current = requests
+            .first()
+            .map(|request| (0, SearchJob::start(request.clone())));
+        Self {
+            requests,
+            current,
+            cancelled: false,
+        }
+    }
+
+    /// Returns the rows found since the last call, each with the index of its request.
+    pub fn poll(&mut self) -> Vec<(usize, Vec<ResultRow>)> {
+        let mut found = vec![];
+        while let Some((index, job)) = self.current.as_mut() {
+            let index = *index;
+            let rows = job.poll();
+            if !rows.is_empty() {
+                found.push((index, rows));
+            }
+            if !job.is_finished() {
+                break;
+            }
+            self.current = match self.requests.get(index + 1) {
+                Some(request) if !self.cancelled => {
+                    Some((index + 1, SearchJob::start(request.clone())))
+                }
+                _ => None,
+            };
+        }
+        found
+    }
+
+    /// Fraction of the requests searched so far.
+    pub fn progress(&self) -> f32 {
+        match &self.current {
+            Some((index, job)) => (*index as f32 + job.progress()) / self.requests.len() as f32,
+            None => 1.0,
+        }
+    }
+
+    /// Number of requests started so far and the number of requests.
+    pub fn searches(&self) -> (usize, usize) {
+        let started = match &self.current {
+            Some((index, _)) => index + 1,
+            None => self.requests.len(),
+        };
+        (started, self.requests.len())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// Stops the search in progress and skips the remaining requests.
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+        if let Some((_, job)) = self.current.as_mut() {
+            job.cancel();
+        }
+    }
+}
+
+/// Length of the chunk starting at `searched` and whether it is the last one.
+///
+/// An empty range still gets one chunk, since a search of 0 advances checks the first one.
+fn next_chunk(searched: u32, total: u32) -> (u32, bool) {
+    let len = CHUNK_SIZE.min(total - searched);
+    (len, searched + len == total)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use super::Steps;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::sync::mpsc::{channel, Receiver, TryRecvError};
+    use std::sync::Arc;
+    use std::thread;
+
+    pub(super) struct Job<T> {
+        completed: Arc<AtomicU32>,
+        cancelled: Arc<AtomicBool>,
+        receiver: Receiver<T>,
+        finished: bool,
+    }
+
+    impl<T: Send + 'static> Job<T> {
+        pub(super) fn start<S: Steps<Output = T>>(mut steps: S) -> Self {
+            let completed = Arc::new(AtomicU32::new(0));
+            let cancelled = Arc::new(AtomicBool::new(false));
+            let (sender, receiver) = channel();
+
+            let thread_completed = completed.clone();
+            let thread_cancelled = cancelled.clone();
+            thread::spawn(move || {
+                while !thread_cancelled.load(Ordering::Relaxed) {
+                    let (output, last) = steps.step();
+                    thread_completed.store(steps.completed(), Ordering::Relaxed);
+                    if sender.send(output).is_err() || last {
+                        break;
+                    }
+                }
+            });
+
+            Self {
+                completed,
+                cancelled,
+                receiver,
+                finished: false,
+            }
+        }
+
+        pub(super) fn poll(&mut self) -> Vec<T> {
+            let mut outputs = vec![];
+            loop {
+                match self.receiver.try_recv() {
+                    Ok(output) => outputs.push(output),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        self.finished = true;
+                        break;
+                    }
+                }
+            }
+            outputs
+        }
+
+        pub(super) fn completed(&self) -> u32 {
+            self.completed.load(Ordering::Relaxed)
+        }
+
+        pub(super) fn is_finished(&self) -> bool {
+            self.finished
+        }
+
+        pub(super) fn cancel(&mut self) {
+            self.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use super::Steps;
+
+    pub(super) struct Job<S> {
+        steps: S,
+        done: bool,
+        cancelled: bool,
+    }
+
+    impl<S: Steps> Job<S> {
+        pub(super) fn start(steps: S) -> Self {
+            Self {
+                steps,
+                done: false,
+                cancelled: false,
+            }
+        }
+
+        pub(super) fn poll(&mut self) -> Vec<S::Output> {
+            if self.is_finished() {
+                return vec![];
+            }
+            let (output, last) = self.steps.step();
+            self.done = last;
+            vec![output]
+        }
+
+        pub(super) fn completed(&self) -> u32 {
+            self.steps.completed()
+        }
+
+        pub(super) fn is_finished(&self) -> bool {
+            self.cancelled || self.done
+        }
+
+        pub(super) fn cancel(&mut self) {
+            self.cancelled = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::next_chunk;
+    use crate::search::{search, search_range, ResultRow, SearchRequest};
+
+    /// Searches the request the way both jobs do, one chunk at a time.
+    fn search_in_chunks(request: &SearchRequest) -> Vec<ResultRow> {
+        let mut rows = vec![];
+        let mut offset = 0;
+        loop {
+            let (len, last) = next_chunk(offset, request.max_advances);
+            rows.extend(search_range(request, offset, len, last));
+            offset += len;
+            if last {
+                return rows;
+            }
+        }
+    }
+
+    #[test]
+    fn chunks_match_single_search_across_jump_boundary() {
+        // Chunks start at 3000, 5000, 7000 and 9000, so the first is advanced to and the
+        // rest are jumped to.
+        let request = SearchRequest {
+            state: [0x12345678, 0x9ABCDEF0, 0x0FEDCBA9, 0x87654321],
+            min_advances: 3000,
+            max_advances: 6500,
+            ..Default::default()
+        };
+        assert_eq!(search_in_chunks(&request), search(&request).rows);
+    }
+
+    #[test]
+    fn empty_range_is_searched_once() {
+        let request = SearchRequest {
+            state: [1, 2, 3, 4],
+            max_advances: 0,
+            ..Default::default()
+        };
+        assert_eq!(next_chunk(0, 0), (0, true));
+        assert_eq!(search_in_chunks(&request), search(&request).rows);
+    }
+}
@@ -1,28 +1,50 @@
 use bdsp_ug_generator::personal_info_bdsp::PersonalInfoBDSP;
 use bdsp_ug_generator::statues::{get_statue_data, Statue, StatueConfig};
-use bdsp_ug_generator::xorshift::XorShift;
 use bdsp_ug_generator::{
-    available_pokemon, get_available_egg_moves, personal_table, run_results, Filter, RoomType,
-    Version,
+    available_pokemon, get_available_egg_moves, personal_table, RoomType, Version,
 };
+use compare::{DiglettDiff, Hits};
 use eframe::egui::{Context, Visuals};
 use eframe::{egui, CreationContext, Frame};
-use egui_extras::{Size, TableBuilder};
+use encounters::EncounterRate;
+use finder::Observation;
+use job::{BatchJob, SearchJob};
 use lazy_static::lazy_static;
-
-const GENDER_SYMBOLS: [&str; 3] = ["♂", "♀", "-"];
-const SPECIES_EN_RAW: &str = include_str!("../resources/text/other/en/species_en.txt");
-const ABILITIES_EN_RAW: &str = include_str!("../resources/text/other/en/abilities_en.txt");
-const NATURES_EN_RAW: &str = include_str!("../resources/text/other/en/natures_en.txt");
-const MOVES_EN_RAW: &str = include_str!("../resources/text/other/en/moves_en.txt");
-const ITEMS_EN_RAW: &str = include_str!("../resources/text/items/items_en.txt");
-
+use optimizer::{Optimized, OptimizerJob};
+use search::{ResultRow, SearchError, SearchFilter, SearchRequest};
+use statues::StatuePreset;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use table::ResultsView;
+use tracker::Tracker;
+
+pub mod compare;
+mod details;
+pub mod encounters;
+pub mod export;
+pub mod finder;
+pub mod job;
+pub mod optimizer;
+mod persistence;
+pub mod search;
+#[cfg(target_arch = "wasm32")]
+mod share;
+mod statues;
+mod table;
+pub mod text;
+mod tracker;
+
+pub const GENDER_SYMBOLS: [&str; 3] = ["♂", "♀", "-"];
+/// Indices into `TYPES_EN` that hidden power can have, Fighting through Dark.
+pub const HIDDEN_POWER_TYPES: RangeInclusive<usize> = 1..=16;
 lazy_static! {
-    pub static ref SPECIES_EN: Vec<&'static str> = load_string_list(SPECIES_EN_RAW);
-    pub static ref ABILITIES_EN: Vec<&'static str> = load_string_list(ABILITIES_EN_RAW);
-    pub static ref NATURES_EN: Vec<&'static str> = load_string_list(NATURES_EN_RAW);
-    pub static ref MOVES_EN: Vec<&'static str> = load_string_list(MOVES_EN_RAW);
-    pub static ref ITEMS_EN: Vec<&'static str> = load_string_list(ITEMS_EN_RAW);
+    pub static ref SPECIES_EN: Vec<&'static str> = text::english_list("species");
+    pub static ref ABILITIES_EN: Vec<&'static str> = text::english_list("abilities");
+    pub static ref NATURES_EN: Vec<&'static str> = text::english_list("natures");
+    pub static ref MOVES_EN: Vec<&'static str> = text::english_list("moves");
+    pub static ref ITEMS_EN: Vec<&'static str> = text::english_list("items");
+    pub static ref TYPES_EN: Vec<&'static str> = text::english_list("types");
+    pub static ref CHARACTERISTICS_EN: Vec<&'static str> = text::english_list("characteristics");
 }
 
 fn load_string_list(list: &str) -> Vec<&str> {
@@ -41,7 +63,7 @@ fn load_string_list(list: &str) -> Vec<&str> {
 
 #[derive(PartialOrd, PartialEq, Copy, Clone, Default)]
 #[repr(u8)]
-enum StoryFlag {
+pub enum StoryFlag {
     UndergroundUnlocked = 1,
     StrengthObtained,
     DefogObtained,
@@ -52,18 +74,79 @@ enum StoryFlag {
 }
 
 impl StoryFlag {
-    fn get_str(&self) -> &'static str {
-        match self {
-            StoryFlag::UndergroundUnlocked => "Underground Unlocked",
-            StoryFlag::StrengthObtained => "Strength Obtained",
-            StoryFlag::DefogObtained => "Defog Obtained",
-            StoryFlag::SevenBadges => "7 Badges",
-            StoryFlag::WaterfallObtained => "Waterfall Obtained",
-            StoryFlag::NationalDex => "National Dex",
-        }
+    pub const ALL: [StoryFlag; 6] = [
+        StoryFlag::UndergroundUnlocked,
+        StoryFlag::StrengthObtained,
+        StoryFlag::DefogObtained,
+        StoryFlag::SevenBadges,
+        StoryFlag::WaterfallObtained,
+        StoryFlag::NationalDex,
+    ];
+
+    pub fn get_str(&self) -> &'static str {
+        text::ui(match self {
+            StoryFlag::UndergroundUnlocked => "story_underground_unlocked",
+            StoryFlag::StrengthObtained => "story_strength_obtained",
+            StoryFlag::DefogObtained => "story_defog_obtained",
+            StoryFlag::SevenBadges => "story_seven_badges",
+            StoryFlag::WaterfallObtained => "story_waterfall_obtained",
+            StoryFlag::NationalDex => "story_national_dex",
+        })
     }
 }
 
+pub const ROOMS: [RoomType; 18] = [
+    RoomType::SpaciousCave,
+    RoomType::GrasslandCave,
+    RoomType::FountainspringCave,
+    RoomType::RockyCave,
+    RoomType::VolcanicCave,
+    RoomType::SwampyCave,
+    RoomType::DazzlingCave,
+    RoomType::WhiteoutCave,
+    RoomType::IcyCave,
+    RoomType::RiverbankCave,
+    RoomType::SandsearCave,
+    RoomType::StillWaterCavern,
+    RoomType::SunlitCavern,
+    RoomType::BigBluffCavern,
+    RoomType::StargleamCavern,
+    RoomType::GlacialCavern,
+    RoomType::BogsunkCavern,
+    RoomType::TyphloCavern,
+];
+
+/// Room name in the UI language.
+pub fn room_str(room: RoomType) -> &'static str {
+    text::ui(match room {
+        RoomType::SpaciousCave => "room_spacious_cave",
+        RoomType::GrasslandCave => "room_grassland_cave",
+        RoomType::FountainspringCave => "room_fountainspring_cave",
+        RoomType::RockyCave => "room_rocky_cave",
+        RoomType::VolcanicCave => "room_volcanic_cave",
+        RoomType::SwampyCave => "room_swampy_cave",
+        RoomType::DazzlingCave => "room_dazzling_cave",
+        RoomType::WhiteoutCave => "room_whiteout_cave",
+        RoomType::IcyCave => "room_icy_cave",
+        RoomType::RiverbankCave => "room_riverbank_cave",
+        RoomType::SandsearCave => "room_sandsear_cave",
+        RoomType::StillWaterCavern => "room_still_water_cavern",
+        RoomType::SunlitCavern => "room_sunlit_cavern",
+        RoomType::BigBluffCavern => "room_big_bluff_cavern",
+        RoomType::StargleamCavern => "room_stargleam_cavern",
+        RoomType::GlacialCavern => "room_glacial_cavern",
+        RoomType::BogsunkCavern => "room_bogsunk_cavern",
+        RoomType::TyphloCavern => "room_typhlo_cavern",
+    })
+}
+
+pub fn version_str(version: Version) -> &'static str {
+    text::ui(match version {
+        Version::BD => "version_bd",
+        Version::SP => "version_sp",
+    })
+}
+
 pub struct BDSPUgGeneratorUI {
     s0: String,
     s1: String,
@@ -74,6 +157,12 @@ pub struct BDSPUgGeneratorUI {
     delay: u32,
     min_ivs: [u8; 6],
     max_ivs: [u8; 6],
+    min_iv_total: u16,
+    max_iv_total: u16,
+    min_perfect_ivs: u8,
+    zero_atk: bool,
+    zero_spe: bool,
+    hidden_power: Option<usize>,
     version: Version,
     story_flag: StoryFlag,
     room: RoomType,
@@ -85,50 +174,53 @@ pub struct BDSPUgGeneratorUI {
     egg_move: Option<u16>,
     natures: [bool; 25],
     item: Option<u16>,
-    personal_info: Option<&'static PersonalInfoBDSP>,
+    species: Vec<u16>,
     available_pokemon: Vec<u16>,
     available_egg_moves: Vec<u16>,
+    available_items: Vec<u16>,
     show_statues: bool,
+    show_encounters: bool,
+    show_tracker: bool,
+    show_finder: bool,
+    show_room_compare: bool,
+    /// Rooms included in the room comparison, in [`ROOMS`] order.
+    compared_rooms: [bool; 18],
+    room_hits: Option<Vec<(RoomType, Hits)>>,
+    room_job: Option<BatchJob>,
+    show_progress_compare: bool,
+    /// Hits per story flag, in [`compare::VERSIONS`] order.
+    progress_hits: Option<Vec<(StoryFlag, [Hits; 2])>>,
+    progress_job: Option<BatchJob>,
+    show_diglett_compare: bool,
+    diglett_diffs: Option<BTreeMap<u32, DiglettDiff>>,
+    diglett_job: Option<BatchJob>,
+    diglett_only_differing: bool,
+    observations: Vec<Observation>,
+    /// IV text for each observation, kept so partly typed values aren't lost.
+    observation_ivs: Vec<[String; 6]>,
+    found_advances: Option<Vec<u32>>,
+    finder_job: Option<SearchJob>,
+    tracker: Tracker,
+    encounter_rates: Option<Vec<EncounterRate>>,
     statue_data: Vec<(String, Statue)>,
     selected_statue: Option<usize>,
     statue_config: StatueConfig,
-    results: Vec<(
-        String,
-        String,
-        &'static str,
-        &'static str,
-        String,
-        String,
-        String,
-        String,
-        String,
-        String,
-        &'static str,
-        String,
-        &'static str,
-        &'static str,
-        &'static str,
-        String,
-    )>,
-    error: &'static str,
+    statue_presets: Vec<StatuePreset>,
+    selected_preset: Option<usize>,
+    preset_name: String,
+    optimizer_species: Option<u16>,
+    optimizer_job: Option<OptimizerJob>,
+    optimizer_result: Option<Optimized>,
+    results: Vec<ResultRow>,
+    results_view: ResultsView,
+    details_level: u8,
+    job: Option<SearchJob>,
+    error: String,
 }
 
 impl Default for BDSPUgGeneratorUI {
     fn default() -> Self {
-        let statue_data_raw = get_statue_data();
-
-        let mut statue_data = statue_data_raw
-            .into_iter()
-            .map(|s| {
-                if s.rarity == 1 {
-                    (SPECIES_EN[s.mons_id].to_string(), s)
-                } else {
-                    (format!("{} - Rare", SPECIES_EN[s.mons_id]), s)
-                }
-            })
-            .collect::<Vec<(String, Statue)>>();
-
-        statue_data.sort_by(|s1, s2| s1.0.cmp(&s2.0));
+        let statue_data = statues::sorted_statue_data(get_statue_data());
 
         Self {
             s0: "".to_string(),
@@ -140,6 +232,12 @@ impl Default for BDSPUgGeneratorUI {
             delay: 0,
             min_ivs: [0, 0, 0, 0, 0, 0],
             max_ivs: [31, 31, 31, 31, 31, 31],
+            min_iv_total: 0,
+            max_iv_total: 186,
+            min_perfect_ivs: 0,
+            zero_atk: false,
+            zero_spe: false,
+            hidden_power: None,
             version: Version::BD,
             story_flag: StoryFlag::default(),
             room: RoomType::SpaciousCave,
@@ -151,15 +249,45 @@ impl Default for BDSPUgGeneratorUI {
             egg_move: None,
             natures: [false; 25],
             item: None,
-            personal_info: None,
+            species: vec![],
             available_pokemon: available_pokemon(Version::BD, 6, RoomType::SpaciousCave),
             available_egg_moves: vec![],
+            available_items: vec![],
             show_statues: false,
+            show_encounters: false,
+            show_tracker: false,
+            show_finder: false,
+            show_room_compare: false,
+            compared_rooms: [true; 18],
+            room_hits: None,
+            room_job: None,
+            show_progress_compare: false,
+            progress_hits: None,
+            progress_job: None,
+            show_diglett_compare: false,
+            diglett_diffs: None,
+            diglett_job: None,
+            diglett_only_differing: true,
+            observations: vec![],
+            observation_ivs: vec![],
+            found_advances: None,
+            finder_job: None,
+            tracker: Tracker::default(),
+            encounter_rates: None,
             statue_data,
             selected_statue: None,
             statue_config: StatueConfig::default(),
+            statue_presets: vec![],
+            selected_preset: None,
+            preset_name: String::new(),
+            optimizer_species: None,
+            optimizer_job: None,
+            optimizer_result: None,
             results: vec![],
-            error: "",
+            results_view: ResultsView::default(),
+            details_level: 50,
+            job: None,
+            error: String::new(),
         }
     }
 }
@@ -167,79 +295,170 @@ impl Default for BDSPUgGeneratorUI {
 impl BDSPUgGeneratorUI {
     pub fn new(cc: &CreationContext<'_>) -> Self {
         cc.egui_ctx.set_visuals(Visuals::dark());
-        Self::default()
+        let mut app = Self::default();
+        if let Some(storage) = cc.storage {
+            persistence::load(storage, &mut app);
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Some(shared) = share::decode(&cc.integration_info.web_info.location.hash) {
+            shared.apply(&mut app);
+        }
+        app
+    }
+
+    /// Returns the personal info of the selected species when exactly one is selected.
+    fn personal_info(&self) -> Option<&'static PersonalInfoBDSP> {
+        match self.species.as_slice() {
+            [species] => Some(personal_table::BDSP.get_form_entry(*species as usize, 0)),
+            _ => None,
+        }
+    }
+
+    /// Rebuilds the egg move and item choices for the selected species.
+    fn species_changed(&mut self) {
+        self.available_egg_moves.clear();
+        self.available_items.clear();
+        for &species in &self.species {
+            for egg_move in get_available_egg_moves(species) {
+                if !self.available_egg_moves.contains(&egg_move) {
+                    self.available_egg_moves.push(egg_move);
+                }
+            }
+            let personal_info = personal_table::BDSP.get_form_entry(species as usize, 0);
+            for item in [
+                personal_info.get_item_1(),
+                personal_info.get_item_2(),
+                personal_info.get_item_3(),
+            ] {
+                if !self.available_items.contains(&(item as u16)) {
+                    self.available_items.push(item as u16);
+                }
+            }
+        }
+        if matches!(self.egg_move, Some(egg_move) if !self.available_egg_moves.contains(&egg_move))
+        {
+            self.egg_move = None;
+        }
+        if matches!(self.item, Some(item) if !self.available_items.contains(&item)) {
+            self.item = None;
+        }
+    }
+
+    fn export_results(&mut self, extension: &str) {
+        let contents = if extension == "json" {
+            export::to_json(&self.results)
+        } else {
+            export::to_csv(&self.results)
+        };
+        if let Err(e) = export::save_file(&format!("results.{}", extension), extension, &contents) {
+            self.error = e;
+        }
+    }
+
+    /// The search settings without the filter, for tools that compare every spawn.
+    fn unfiltered_request(&self) -> Result<SearchRequest, SearchError> {
+        let request = SearchRequest {
+            state: search::parse_state(&self.s0, &self.s1, &self.s2, &self.s3)?,
+            min_advances: self.min_advances,
+            max_advances: self.max_advances,
+            delay: self.delay,
+            version: self.version,
+            story_flag: self.story_flag,
+            room: self.room,
+            diglett_mode: self.diglett_mode,
+            statues: self.statue_config.statues.clone(),
+            filter: SearchFilter::default(),
+        };
+        request.validate()?;
+        Ok(request)
+    }
+
+    fn search_request(&self) -> Result<SearchRequest, SearchError> {
+        Ok(SearchRequest {
+            filter: SearchFilter {
+                shiny: self.shiny,
+                species: self.species.clone(),
+                min_ivs: self.min_ivs,
+                max_ivs: self.max_ivs,
+                ability: self.ability,
+                nature: {
+                    let natures = self
+                        .natures
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, &n)| if n { Some(i as u8) } else { None })
+                        .collect::<Vec<u8>>();
+                    if natures.is_empty() {
+                        None
+                    } else {
+                        Some(natures)
+                    }
+                },
+                item: self.item,
+                egg_move: self.egg_move,
+                gender: self.gender,
+                exclusive: self.exclusive,
+                min_perfect_ivs: self.min_perfect_ivs,
+                min_iv_total: self.min_iv_total,
+                max_iv_total: self.max_iv_total,
+                zero_atk: self.zero_atk,
+                zero_spe: self.zero_spe,
+                hidden_power: self.hidden_power,
+            },
+            ..self.unfiltered_request()?
+        })
     }
 }
 
 impl eframe::App for BDSPUgGeneratorUI {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        persistence::save(storage, self);
+    }
+
     fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+        if let Some(job) = self.job.as_mut() {
+            let rows = job.poll();
+            if !rows.is_empty() {
+                self.results.extend(rows);
+                self.results_view.invalidate();
+            }
+            if job.is_finished() {
+                self.job = None;
+            } else {
+                ctx.request_repaint();
+            }
+        }
+
         if self.show_statues {
-            egui::Window::new("Statue Config").show(ctx, |ui| {
-                egui::ComboBox::new("statues", "")
-                    .selected_text(if let Some(index) = self.selected_statue.as_ref() {
-                        &self.statue_data[*index].0
-                    } else {
-                        "None"
-                    })
-                    .width(155.0)
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.selected_statue, None, "None");
-                        self.statue_data
-                            .iter()
-                            .enumerate()
-                            .for_each(|(index, (name, _))| {
-                                ui.selectable_value(
-                                    &mut self.selected_statue,
-                                    Some(index),
-                                    name.as_str(),
-                                );
-                            });
-                    });
-                ui.horizontal(|ui| {
-                    if ui.button("Add").clicked() {
-                        if let Some(index) = self.selected_statue.as_ref() {
-                            self.statue_config.add_statue(self.statue_data[*index].1);
-                        }
-                    }
+            self.show_statue_window(ctx);
+        }
 
-                    if ui.button("Remove Last").clicked() {
-                        self.statue_config.statues.pop();
-                    }
+        if self.show_encounters {
+            self.show_encounter_window(ctx);
+        }
 
-                    if ui.button("Close").clicked() {
-                        self.show_statues = false;
-                    }
-                });
+        if self.show_tracker {
+            self.show_tracker_window(ctx);
+        }
 
-                ui.add_space(10.0);
+        if self.show_finder {
+            self.show_finder_window(ctx);
+        }
 
-                ui.push_id("statue_table", |ui| {
-                    TableBuilder::new(ui)
-                        .striped(false)
-                        .clip(false)
-                        .column(Size::exact(150.0))
-                        .resizable(false)
-                        .header(25.0, |mut header| {
-                            header.col(|ui| {
-                                ui.heading("Statues");
-                            });
-                        })
-                        .body(|b| {
-                            b.rows(15.0, self.statue_config.statues.len(), |index, mut r| {
-                                let statue = &self.statue_config.statues[index];
-                                r.col(|ui| {
-                                    if statue.rarity == 1 {
-                                        ui.label(SPECIES_EN[statue.mons_id]);
-                                    } else {
-                                        ui.label(format!("{} - Rare", SPECIES_EN[statue.mons_id]));
-                                    }
-                                });
-                            });
-                        });
-                });
-            });
+        if self.show_room_compare {
+            self.show_room_compare_window(ctx);
+        }
+
+        if self.show_progress_compare {
+            self.show_progress_compare_window(ctx);
+        }
+
+        if self.show_diglett_compare {
+            self.show_diglett_compare_window(ctx);
         }
 
+        self.show_details_panel(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.vertical(|ui| {
@@ -247,79 +466,69 @@ impl eframe::App for BDSPUgGeneratorUI {
                         .num_columns(2)
                         .spacing([5.0, 5.0])
                         .show(ui, |ui| {
-                            ui.label("s0");
-                            let output = egui::TextEdit::singleline(&mut self.s0)
-                                .desired_width(150.0)
-                                .show(ui);
-                            if output.response.changed() {
-                                if self.s0.chars().count() > 8 {
-                                    self.s0 = self.s0.chars().take(8).collect();
+                            let mut pasted = None;
+                            for (i, seed) in
+                                [&mut self.s0, &mut self.s1, &mut self.s2, &mut self.s3]
+                                    .into_iter()
+                                    .enumerate()
+                            {
+                                ui.label(format!("s{}", i));
+                                let valid = seed.is_empty() || search::parse_seed(seed).is_some();
+                                let mut edit =
+                                    egui::TextEdit::singleline(seed).desired_width(150.0);
+                                if !valid {
+                                    edit = edit.text_color(egui::Color32::RED);
                                 }
-                            }
-                            ui.end_row();
-                            ui.label("s1");
-                            let output = egui::TextEdit::singleline(&mut self.s1)
-                                .desired_width(150.0)
-                                .show(ui);
-                            if output.response.changed() {
-                                if self.s1.chars().count() > 8 {
-                                    self.s1 = self.s1.chars().take(8).collect();
+                                let output = edit.show(ui);
+                                if output.response.changed() {
+                                    if let Some(state) = search::split_state(seed) {
+                                        pasted = Some(state);
+                                    } else {
+                                        if seed.starts_with("0x") || seed.starts_with("0X") {
+                                            seed.drain(..2);
+                                        }
+                                        if seed.chars().count() > 8 {
+                                            *seed = seed.chars().take(8).collect();
+                                        }
+                                    }
                                 }
-                            }
-                            ui.end_row();
-                            ui.label("s2");
-                            let output = egui::TextEdit::singleline(&mut self.s2)
-                                .desired_width(150.0)
-                                .show(ui);
-                            if output.response.changed() {
-                                if self.s2.chars().count() > 8 {
-                                    self.s2 = self.s2.chars().take(8).collect();
+                                if !valid {
+                                    output.response.on_hover_text(text::ui("seed_hint"));
                                 }
+                                ui.end_row();
                             }
-                            ui.end_row();
-                            ui.label("s3");
-                            let output = egui::TextEdit::singleline(&mut self.s3)
-                                .desired_width(150.0)
-                                .show(ui);
-                            if output.response.changed() {
-                                if self.s3.chars().count() > 8 {
-                                    self.s3 = self.s3.chars().take(8).collect();
-                                }
+                            if let Some(state) = pasted {
+                                self.s0 = format!("{:08X}", state[0]);
+                                self.s1 = format!("{:08X}", state[1]);
+                                self.s2 = format!("{:08X}", state[2]);
+                                self.s3 = format!("{:08X}", state[3]);
                             }
-                            ui.end_row();
-                            ui.label("Min Advances");
+                            ui.label(text::ui("min_advances"));
                             ui.add(egui::DragValue::new(&mut self.min_advances));
                             ui.end_row();
-                            ui.label("Max Advances");
+                            ui.label(text::ui("max_advances"));
                             ui.add(egui::DragValue::new(&mut self.max_advances));
                             ui.end_row();
-                            ui.label("Delay");
+                            ui.label(text::ui("delay"));
                             ui.add(egui::DragValue::new(&mut self.delay));
                             ui.end_row();
 
-                            ui.label("Version");
+                            ui.label(text::ui("version"));
                             egui::ComboBox::from_id_source("cmb_version")
                                 .width(150.0)
-                                .selected_text(match self.version {
-                                    Version::BD => "Brilliant Diamond",
-                                    Version::SP => "Shining Pearl",
-                                })
+                                .selected_text(version_str(self.version))
                                 .show_ui(ui, |ui| {
-                                    if ui
-                                        .selectable_value(
-                                            &mut self.version,
-                                            Version::BD,
-                                            "Brilliant Diamond",
-                                        )
-                                        .clicked()
-                                        || ui
+                                    let mut changed = false;
+                                    for version in [Version::BD, Version::SP] {
+                                        changed |= ui
                                             .selectable_value(
                                                 &mut self.version,
-                                                Version::SP,
-                                                "Shining Pearl",
+                                                version,
+                                                version_str(version),
                                             )
-                                            .clicked()
-                                    {
+                                            .clicked();
+                                    }
+                                    if changed {
                                         self.available_pokemon = available_pokemon(
                                             self.version,
                                             self.story_flag as u8,
@@ -329,54 +538,22 @@ impl eframe::App for BDSPUgGeneratorUI {
                                 });
                             ui.end_row();
 
-                            ui.label("Story Flag");
+                            ui.label(text::ui("story_flag"));
                             egui::ComboBox::from_id_source("cmb_story_flag")
                                 .width(150.0)
                                 .selected_text(self.story_flag.get_str())
                                 .show_ui(ui, |ui| {
-                                    if ui
-                                        .selectable_value(
-                                            &mut self.story_flag,
-                                            StoryFlag::UndergroundUnlocked,
-                                            StoryFlag::UndergroundUnlocked.get_str(),
-                                        )
-                                        .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.story_flag,
-                                                StoryFlag::StrengthObtained,
-                                                StoryFlag::StrengthObtained.get_str(),
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.story_flag,
-                                                StoryFlag::DefogObtained,
-                                                StoryFlag::DefogObtained.get_str(),
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.story_flag,
-                                                StoryFlag::SevenBadges,
-                                                StoryFlag::SevenBadges.get_str(),
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.story_flag,
-                                                StoryFlag::WaterfallObtained,
-                                                StoryFlag::WaterfallObtained.get_str(),
-                                            )
-                                            .clicked()
-                                        || ui
+                                    let mut changed = false;
+                                    for story_flag in StoryFlag::ALL {
+                                        changed |= ui
                                             .selectable_value(
                                                 &mut self.story_flag,
-                                                StoryFlag::NationalDex,
-                                                StoryFlag::NationalDex.get_str(),
+                                                story_flag,
+                                                story_flag.get_str(),
                                             )
-                                            .clicked()
-                                    {
+                                            .clicked();
+                                    }
+                                    if changed {
                                         self.available_pokemon = available_pokemon(
                                             self.version,
                                             self.story_flag as u8,
@@ -386,157 +563,18 @@ impl eframe::App for BDSPUgGeneratorUI {
                                 });
                             ui.end_row();
 
-                            ui.label("Room");
+                            ui.label(text::ui("room"));
                             egui::ComboBox::from_id_source("cmb_room")
                                 .width(150.0)
-                                .selected_text(match self.room {
-                                    RoomType::SpaciousCave => "Spacious Cave",
-                                    RoomType::GrasslandCave => "Grassland Cave",
-                                    RoomType::FountainspringCave => "Fountainspring Cave",
-                                    RoomType::RockyCave => "Rocky Cave",
-                                    RoomType::VolcanicCave => "Volcanic Cave",
-                                    RoomType::SwampyCave => "Swampy Cave",
-                                    RoomType::DazzlingCave => "Dazzling Cave",
-                                    RoomType::WhiteoutCave => "Whiteout Cave",
-                                    RoomType::IcyCave => "Icy Cave",
-                                    RoomType::RiverbankCave => "Riverbank Cave",
-                                    RoomType::SandsearCave => "Sandsear Cave",
-                                    RoomType::StillWaterCavern => "Still Water Cavern",
-                                    RoomType::SunlitCavern => "Sunlit Cavern",
-                                    RoomType::BigBluffCavern => "Big Bluff Cavern",
-                                    RoomType::StargleamCavern => "Stargleam Cavern",
-                                    RoomType::GlacialCavern => "Glacial Cavern",
-                                    RoomType::BogsunkCavern => "Bogsunk Cavern",
-                                    RoomType::TyphloCavern => "Typhlo Cavern",
-                                })
+                                .selected_text(room_str(self.room))
                                 .show_ui(ui, |ui| {
-                                    if ui
-                                        .selectable_value(
-                                            &mut self.room,
-                                            RoomType::SpaciousCave,
-                                            "Spacious Cave",
-                                        )
-                                        .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::GrasslandCave,
-                                                "Grassland Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::FountainspringCave,
-                                                "Fountainspring Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::RockyCave,
-                                                "Rocky Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::VolcanicCave,
-                                                "Volcanic Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::SwampyCave,
-                                                "Swampy Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::DazzlingCave,
-                                                "Dazzling Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::WhiteoutCave,
-                                                "Whiteout Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::IcyCave,
-                                                "Icy Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::RiverbankCave,
-                                                "Riverbank Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::SandsearCave,
-                                                "Sandsear Cave",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::StillWaterCavern,
-                                                "Still Water Cavern",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::SunlitCavern,
-                                                "Sunlit Cavern",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::BigBluffCavern,
-                                                "Big Bluff Cavern",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::StargleamCavern,
-                                                "Stargleam Cavern",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::GlacialCavern,
-                                                "Glacial Cavern",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::BogsunkCavern,
-                                                "Bogsunk Cavern",
-                                            )
-                                            .clicked()
-                                        || ui
-                                            .selectable_value(
-                                                &mut self.room,
-                                                RoomType::TyphloCavern,
-                                                "Typhlo Cavern",
-                                            )
-                                            .clicked()
-                                    {
+                                    let mut changed = false;
+                                    for room in ROOMS {
+                                        changed |= ui
+                                            .selectable_value(&mut self.room, room, room_str(room))
+                                            .clicked();
+                                    }
+                                    if changed {
                                         self.available_pokemon = available_pokemon(
                                             self.version,
                                             self.story_flag as u8,
@@ -546,50 +584,62 @@ impl eframe::App for BDSPUgGeneratorUI {
                                 });
                             ui.end_row();
 
-                            ui.label("Diglett Mode");
+                            ui.label(text::ui("diglett_mode"));
                             ui.checkbox(&mut self.diglett_mode, "");
                             ui.end_row();
 
-                            ui.label("Species");
+                            ui.label(text::ui("species"));
                             egui::ComboBox::from_id_source("cmb_species")
                                 .width(150.0)
-                                .selected_text(if let Some(personal_info) = self.personal_info {
-                                    SPECIES_EN[personal_info.get_species()]
+                                .selected_text(if self.species.is_empty() {
+                                    text::ui("any").to_string()
                                 } else {
-                                    "None"
+                                    self.species
+                                        .iter()
+                                        .map(|&species| text::species(species as usize))
+                                        .collect::<Vec<&str>>()
+                                        .join(",")
                                 })
                                 .show_ui(ui, |ui| {
-                                    if ui.selectable_label(false, "Any").clicked() {
-                                        self.personal_info = None;
+                                    let mut changed = false;
+                                    if ui.selectable_label(false, text::ui("any")).clicked() {
+                                        self.species.clear();
+                                        changed = true;
                                     }
                                     for &p in &self.available_pokemon {
+                                        let mut selected = self.species.contains(&p);
                                         if ui
-                                            .selectable_label(false, SPECIES_EN[p as usize])
-                                            .clicked()
+                                            .checkbox(&mut selected, text::species(p as usize))
+                                            .changed()
                                         {
-                                            self.personal_info = Some(
-                                                personal_table::BDSP.get_form_entry(p as usize, 0),
-                                            );
-                                            self.available_egg_moves = get_available_egg_moves(p);
+                                            if selected {
+                                                self.species.push(p);
+                                            } else {
+                                                self.species.retain(|&species| species != p);
+                                            }
+                                            changed = true;
                                         }
                                     }
+                                    if changed {
+                                        self.species_changed();
+                                    }
                                 });
                             ui.end_row();
 
-                            ui.label("Shiny");
+                            ui.label(text::ui("shiny"));
                             ui.checkbox(&mut self.shiny, "");
                             ui.end_row();
 
-                            ui.label("Gender");
+                            ui.label(text::ui("gender"));
                             egui::ComboBox::from_id_source("cmb_gender")
                                 .width(150.0)
                                 .selected_text(if let Some(i) = self.gender {
                                     GENDER_SYMBOLS[i as usize]
                                 } else {
-                                    "Any"
+                                    text::ui("any")
                                 })
                                 .show_ui(ui, |ui| {
-                                    ui.selectable_value(&mut self.gender, None, "Any");
+                                    ui.selectable_value(&mut self.gender, None, text::ui("any"));
                                     ui.selectable_value(
                                         &mut self.gender,
                                         Some(0),
@@ -608,71 +658,70 @@ impl eframe::App for BDSPUgGeneratorUI {
                                 });
                             ui.end_row();
 
-                            ui.label("Nature");
+                            ui.label(text::ui("nature"));
                             egui::ComboBox::from_id_source("cmb_nature")
                                 .width(150.0)
                                 .selected_text(if self.natures.iter().all(|&n| n == false) {
-                                    "Any".to_string()
+                                    text::ui("any").to_string()
                                 } else {
                                     let mut s = String::new();
                                     for (i, nature) in self.natures.iter().enumerate() {
                                         if *nature {
                                             if s.is_empty() {
-                                                s = NATURES_EN[i].to_string();
+                                                s = text::nature(i).to_string();
                                             } else {
-                                                s = format!("{},{}", s, NATURES_EN[i]);
+                                                s = format!("{},{}", s, text::nature(i));
                                             }
                                         }
                                     }
                                     s
                                 })
                                 .show_ui(ui, |ui| {
-                                    if ui.selectable_label(false, "Clear").clicked() {
+                                    if ui.selectable_label(false, text::ui("clear")).clicked() {
                                         for nature in self.natures.iter_mut() {
                                             *nature = false;
                                         }
                                     }
                                     for i in 0..25 {
-                                        ui.checkbox(&mut self.natures[i], NATURES_EN[i]);
+                                        ui.checkbox(&mut self.natures[i], text::nature(i));
                                     }
                                 });
                             ui.end_row();
 
-                            ui.label("Ability");
+                            ui.label(text::ui("ability"));
+                            let personal_info = self.personal_info();
                             egui::ComboBox::from_id_source("cmb_ability")
-                                .selected_text(if let Some(personal_info) = &self.personal_info {
+                                .selected_text(if let Some(personal_info) = personal_info {
                                     if let Some(ability) = &self.ability {
                                         if *ability == 0 {
-                                            ABILITIES_EN[personal_info.get_ability_1()]
+                                            text::ability(personal_info.get_ability_1())
                                         } else {
-                                            ABILITIES_EN[personal_info.get_ability_2()]
+                                            text::ability(personal_info.get_ability_2())
                                         }
                                     } else {
-                                        "Any"
+                                        text::ui("any")
                                     }
-                                } else {
-                                    if let Some(ability) = &self.ability {
-                                        if *ability == 0 {
-                                            "1"
-                                        } else {
-                                            "2"
-                                        }
+                                } else if let Some(ability) = &self.ability {
+                                    if *ability == 0 {
+                                        "1"
                                     } else {
-                                        "Any"
+                                        "2"
                                     }
+                                } else {
+                                    text::ui("any")
                                 })
                                 .show_ui(ui, |ui| {
-                                    ui.selectable_value(&mut self.ability, None, "Any");
-                                    if let Some(personal_info) = &self.personal_info {
+                                    ui.selectable_value(&mut self.ability, None, text::ui("any"));
+                                    if let Some(personal_info) = personal_info {
                                         ui.selectable_value(
                                             &mut self.ability,
                                             Some(0),
-                                            ABILITIES_EN[personal_info.get_ability_1()],
+                                            text::ability(personal_info.get_ability_1()),
                                         );
                                         ui.selectable_value(
                                             &mut self.ability,
                                             Some(1),
-                                            ABILITIES_EN[personal_info.get_ability_2()],
+                                            text::ability(personal_info.get_ability_2()),
                                         );
                                     } else {
                                         ui.selectable_value(&mut self.ability, Some(0), "1");
@@ -681,247 +730,111 @@ impl eframe::App for BDSPUgGeneratorUI {
                                 });
                             ui.end_row();
 
-                            ui.label("Egg Move");
+                            ui.label(text::ui("egg_move"));
                             egui::ComboBox::from_id_source("cmb_egg_move")
                                 .width(150.0)
-                                .selected_text(if self.personal_info.is_some() {
-                                    if let Some(egg_move) = &self.egg_move {
-                                        MOVES_EN[*egg_move as usize]
-                                    } else {
-                                        "Any"
-                                    }
+                                .selected_text(if let Some(egg_move) = &self.egg_move {
+                                    text::move_name(*egg_move as usize)
                                 } else {
-                                    "Any"
+                                    text::ui("any")
                                 })
                                 .show_ui(ui, |ui| {
-                                    ui.selectable_value(&mut self.egg_move, None, "Any");
-                                    if self.personal_info.is_some() {
-                                        for &available_egg_move in &self.available_egg_moves {
-                                            ui.selectable_value(
-                                                &mut self.egg_move,
-                                                Some(available_egg_move),
-                                                MOVES_EN[available_egg_move as usize],
-                                            );
-                                        }
+                                    ui.selectable_value(&mut self.egg_move, None, text::ui("any"));
+                                    for &available_egg_move in &self.available_egg_moves {
+                                        ui.selectable_value(
+                                            &mut self.egg_move,
+                                            Some(available_egg_move),
+                                            text::move_name(available_egg_move as usize),
+                                        );
                                     }
                                 });
                             ui.end_row();
 
-                            ui.label("Item");
+                            ui.label(text::ui("item"));
                             egui::ComboBox::from_id_source("cmb_item")
                                 .width(150.0)
-                                .selected_text(if self.personal_info.is_some() {
-                                    if let Some(item) = &self.item {
-                                        ITEMS_EN[*item as usize]
-                                    } else {
-                                        "Any"
-                                    }
+                                .selected_text(if let Some(item) = &self.item {
+                                    text::item(*item as usize)
                                 } else {
-                                    "Any"
+                                    text::ui("any")
                                 })
                                 .show_ui(ui, |ui| {
-                                    ui.selectable_value(&mut self.item, None, "Any");
-                                    if let Some(personal_info) = &self.personal_info {
+                                    ui.selectable_value(&mut self.item, None, text::ui("any"));
+                                    for &available_item in &self.available_items {
                                         ui.selectable_value(
                                             &mut self.item,
-                                            Some(personal_info.get_item_1() as u16),
-                                            ITEMS_EN[personal_info.get_item_1()],
-                                        );
-                                        ui.selectable_value(
-                                            &mut self.item,
-                                            Some(personal_info.get_item_2() as u16),
-                                            ITEMS_EN[personal_info.get_item_2()],
-                                        );
-                                        ui.selectable_value(
-                                            &mut self.item,
-                                            Some(personal_info.get_item_3() as u16),
-                                            ITEMS_EN[personal_info.get_item_3()],
+                                            Some(available_item),
+                                            text::item(available_item as usize),
                                         );
                                     }
                                 });
                             ui.end_row();
-                            ui.label("Exclusive Search");
+                            ui.label(text::ui("exclusive_search"));
                             ui.checkbox(&mut self.exclusive, "");
                             ui.end_row();
                         });
-                    if ui.button("Statues").clicked() {
-                        self.show_statues = true;
-                    }
+                    ui.horizontal(|ui| {
+                        if ui.button(text::ui("statues")).clicked() {
+                            self.show_statues = true;
+                        }
+                        if ui.button(text::ui("encounters")).clicked() {
+                            self.show_encounters = true;
+                        }
+                        if ui.button(text::ui("tracker")).clicked() {
+                            self.show_tracker = true;
+                            self.tracker.invalidate();
+                        }
+                        if ui.button(text::ui("find_state")).clicked() {
+                            self.show_finder = true;
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        if ui.button(text::ui("compare_rooms")).clicked() {
+                            self.show_room_compare = true;
+                        }
+                        if ui.button(text::ui("compare_progress")).clicked() {
+                            self.show_progress_compare = true;
+                        }
+                        if ui.button(text::ui("compare_diglett")).clicked() {
+                            self.show_diglett_compare = true;
+                        }
+                    });
                     ui.add_space(5.0);
-                    if ui.button("Search").clicked() {
-                        if let Ok(s0) = u32::from_str_radix(&self.s0, 16) {
-                            if let Ok(s1) = u32::from_str_radix(&self.s1, 16) {
-                                if let Ok(s2) = u32::from_str_radix(&self.s2, 16) {
-                                    if let Ok(s3) = u32::from_str_radix(&self.s3, 16) {
-                                        let filter = Filter {
-                                            shiny: self.shiny,
-                                            species: if let Some(personal_info) =
-                                                &self.personal_info
-                                            {
-                                                Some(personal_info.get_species() as u16)
-                                            } else {
-                                                None
-                                            },
-                                            min_ivs: self.min_ivs,
-                                            max_ivs: self.max_ivs,
-                                            ability: self.ability,
-                                            nature: {
-                                                let natures = self
-                                                    .natures
-                                                    .iter()
-                                                    .enumerate()
-                                                    .filter_map(|(i, &n)| {
-                                                        if n == true {
-                                                            Some(i as u8)
-                                                        } else {
-                                                            None
-                                                        }
-                                                    })
-                                                    .collect::<Vec<u8>>();
-                                                if natures.is_empty() {
-                                                    None
-                                                } else {
-                                                    Some(natures)
-                                                }
-                                            },
-                                            item: self.item,
-                                            egg_move: self.egg_move,
-                                            gender: self.gender,
-                                            exclusive: self.exclusive,
-                                        };
-
-                                        let mut rng = XorShift::from_state([s0, s1, s2, s3]);
-                                        if self.min_advances < 4096 {
-                                            rng.advance(
-                                                self.min_advances as usize + self.delay as usize,
-                                            );
-                                        } else {
-                                            rng.jump(
-                                                self.min_advances as usize + self.delay as usize,
-                                            );
-                                        }
-
-                                        let results = run_results(
-                                            self.max_advances,
-                                            rng,
-                                            self.version,
-                                            self.story_flag as u8,
-                                            self.room,
-                                            filter,
-                                            self.diglett_mode,
-                                            &self.statue_config,
-                                        );
-                                        let mut count = 0;
-                                        for result in results.iter() {
-                                            count += result.regular_pokemon.len();
-                                            if result.rare_pokemon.is_some() {
-                                                count += 1;
-                                            }
-                                        }
-                                        self.results = Vec::with_capacity(count);
-                                        for result in results {
-                                            for pokemon in result.regular_pokemon {
-                                                let personal_info = if let Some(personal_info) =
-                                                    &self.personal_info
-                                                {
-                                                    *personal_info
-                                                } else {
-                                                    personal_table::BDSP
-                                                        .get_form_entry(pokemon.species as usize, 0)
-                                                };
-                                                let ability = if pokemon.ability == 0 {
-                                                    personal_info.get_ability_1()
-                                                } else {
-                                                    personal_info.get_ability_2()
-                                                };
-
-                                                let egg_move =
-                                                    if let Some(egg_move) = &pokemon.egg_move {
-                                                        *egg_move
-                                                    } else {
-                                                        0
-                                                    };
-
-                                                self.results.push((
-                                                    (result.advance + self.min_advances)
-                                                        .to_string(),
-                                                    format!("{:X}", pokemon.pid),
-                                                    SPECIES_EN[pokemon.species as usize],
-                                                    if pokemon.shiny { "!!!" } else { "X" },
-                                                    pokemon.ivs[0].to_string(),
-                                                    pokemon.ivs[1].to_string(),
-                                                    pokemon.ivs[2].to_string(),
-                                                    pokemon.ivs[3].to_string(),
-                                                    pokemon.ivs[4].to_string(),
-                                                    pokemon.ivs[5].to_string(),
-                                                    ABILITIES_EN[ability],
-                                                    GENDER_SYMBOLS[pokemon.gender as usize]
-                                                        .to_string(),
-                                                    NATURES_EN[pokemon.nature as usize],
-                                                    ITEMS_EN[pokemon.item as usize],
-                                                    MOVES_EN[egg_move as usize],
-                                                    format!("{:X}", pokemon.ec),
-                                                ));
-                                            }
-
-                                            if let Some(pokemon) = result.rare_pokemon {
-                                                let personal_info = if let Some(personal_info) =
-                                                    &self.personal_info
-                                                {
-                                                    *personal_info
-                                                } else {
-                                                    personal_table::BDSP
-                                                        .get_form_entry(pokemon.species as usize, 0)
-                                                };
-                                                let ability = if pokemon.ability == 0 {
-                                                    personal_info.get_ability_1()
-                                                } else {
-                                                    personal_info.get_ability_2()
-                                                };
-
-                                                let egg_move =
-                                                    if let Some(egg_move) = &pokemon.egg_move {
-                                                        *egg_move
-                                                    } else {
-                                                        0
-                                                    };
-
-                                                self.results.push((
-                                                    (result.advance + self.min_advances)
-                                                        .to_string(),
-                                                    format!("{:X}", pokemon.pid),
-                                                    SPECIES_EN[pokemon.species as usize],
-                                                    if pokemon.shiny { "!!!" } else { "X" },
-                                                    pokemon.ivs[0].to_string(),
-                                                    pokemon.ivs[1].to_string(),
-                                                    pokemon.ivs[2].to_string(),
-                                                    pokemon.ivs[3].to_string(),
-                                                    pokemon.ivs[4].to_string(),
-                                                    pokemon.ivs[5].to_string(),
-                                                    ABILITIES_EN[ability],
-                                                    GENDER_SYMBOLS[pokemon.gender as usize]
-                                                        .to_string(),
-                                                    NATURES_EN[pokemon.nature as usize],
-                                                    ITEMS_EN[pokemon.item as usize],
-                                                    MOVES_EN[egg_move as usize],
-                                                    format!("{:X}", pokemon.ec),
-                                                ));
-                                            }
-                                        }
-                                    } else {
-                                        self.error = "Failed to parse s3";
-                                    }
-                                } else {
-                                    self.error = "Failed to parse s2";
-                                }
-                            } else {
-                                self.error = "Failed to parse s1";
+                    if let Some(job) = self.job.as_mut() {
+                        ui.horizontal(|ui| {
+                            if ui.button(text::ui("cancel")).clicked() {
+                                job.cancel();
+                            }
+                            ui.add(
+                                egui::ProgressBar::new(job.progress())
+                                    .desired_width(150.0)
+                                    .text(format!("{} / {}", job.searched(), job.total())),
+                            );
+                        });
+                    } else if ui.button(text::ui("search")).clicked() {
+                        match self.search_request() {
+                            Ok(request) => {
+                                self.results.clear();
+                                self.results_view.clear_selection();
+                                self.results_view.invalidate();
+                                self.job = Some(SearchJob::start(request));
+                                self.error.clear();
                             }
-                        } else {
-                            self.error = "Failed to parse s0";
+                            Err(e) => self.error = e.to_string(),
+                        }
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    if ui
+                        .button(text::ui("copy_link"))
+                        .on_hover_text(text::ui("copy_link_hint"))
+                        .clicked()
+                    {
+                        match share::link(&persistence::SavedInputs::from_ui(self)) {
+                            Some(link) => ui.output().copied_text = link,
+                            None => self.error = text::ui("copy_link_failed").to_string(),
                         }
                     }
-                    ui.label(self.error);
+                    ui.label(&self.error);
                 });
                 ui.add_space(10.0);
                 ui.vertical(|ui| {
@@ -929,160 +842,97 @@ impl eframe::App for BDSPUgGeneratorUI {
                         .num_columns(3)
                         .spacing([5.0, 5.0])
                         .show(ui, |ui| {
-                            ui.label("HP");
+                            ui.label(text::ui("column_hp"));
                             ui.add(egui::DragValue::new(&mut self.min_ivs[0]).clamp_range(0..=31));
                             ui.add(egui::DragValue::new(&mut self.max_ivs[0]).clamp_range(0..=31));
                             ui.end_row();
-                            ui.label("ATK");
+                            ui.label(text::ui("column_atk"));
                             ui.add(egui::DragValue::new(&mut self.min_ivs[1]).clamp_range(0..=31));
                             ui.add(egui::DragValue::new(&mut self.max_ivs[1]).clamp_range(0..=31));
                             ui.end_row();
-                            ui.label("DEF");
+                            ui.label(text::ui("column_def"));
                             ui.add(egui::DragValue::new(&mut self.min_ivs[2]).clamp_range(0..=31));
                             ui.add(egui::DragValue::new(&mut self.max_ivs[2]).clamp_range(0..=31));
                             ui.end_row();
-                            ui.label("SPA");
+                            ui.label(text::ui("column_spa"));
                             ui.add(egui::DragValue::new(&mut self.min_ivs[3]).clamp_range(0..=31));
                             ui.add(egui::DragValue::new(&mut self.max_ivs[3]).clamp_range(0..=31));
                             ui.end_row();
-                            ui.label("SPD");
+                            ui.label(text::ui("column_spd"));
                             ui.add(egui::DragValue::new(&mut self.min_ivs[4]).clamp_range(0..=31));
                             ui.add(egui::DragValue::new(&mut self.max_ivs[4]).clamp_range(0..=31));
                             ui.end_row();
-                            ui.label("SPE");
+                            ui.label(text::ui("column_spe"));
                             ui.add(egui::DragValue::new(&mut self.min_ivs[5]).clamp_range(0..=31));
                             ui.add(egui::DragValue::new(&mut self.max_ivs[5]).clamp_range(0..=31));
                             ui.end_row();
+                            ui.label(text::ui("total"));
+                            ui.add(
+                                egui::DragValue::new(&mut self.min_iv_total).clamp_range(0..=186),
+                            );
+                            ui.add(
+                                egui::DragValue::new(&mut self.max_iv_total).clamp_range(0..=186),
+                            );
+                            ui.end_row();
                         });
-                });
-                ui.add_space(10.0);
-                ui.vertical(|ui| {
-                    TableBuilder::new(ui)
-                        .striped(true)
-                        .cell_layout(egui::Layout::centered_and_justified(
-                            egui::Direction::LeftToRight,
-                        ))
-                        .column(Size::initial(80.0).at_least(80.0))
-                        .column(Size::initial(80.0).at_least(80.0))
-                        .column(Size::initial(100.0).at_least(100.0))
-                        .column(Size::initial(60.0).at_least(60.0))
-                        .column(Size::initial(40.0).at_least(40.0))
-                        .column(Size::initial(40.0).at_least(40.0))
-                        .column(Size::initial(40.0).at_least(40.0))
-                        .column(Size::initial(40.0).at_least(40.0))
-                        .column(Size::initial(40.0).at_least(40.0))
-                        .column(Size::initial(40.0).at_least(40.0))
-                        .column(Size::initial(100.0).at_least(100.0))
-                        .column(Size::initial(70.0).at_least(70.0))
-                        .column(Size::initial(70.0).at_least(70.0))
-                        .column(Size::initial(70.0).at_least(70.0))
-                        .column(Size::initial(100.0).at_least(100.0))
-                        .column(Size::initial(80.0).at_least(80.0))
-                        .resizable(true)
-                        .header(20.0, |mut header| {
-                            header.col(|ui| {
-                                ui.heading("Advance");
-                            });
-                            header.col(|ui| {
-                                ui.heading("PID");
-                            });
-                            header.col(|ui| {
-                                ui.heading("Species");
-                            });
-                            header.col(|ui| {
-                                ui.heading("Shiny");
-                            });
-                            header.col(|ui| {
-                                ui.heading("HP");
-                            });
-                            header.col(|ui| {
-                                ui.heading("ATK");
-                            });
-                            header.col(|ui| {
-                                ui.heading("DEF");
-                            });
-                            header.col(|ui| {
-                                ui.heading("SPA");
-                            });
-                            header.col(|ui| {
-                                ui.heading("SPD");
-                            });
-                            header.col(|ui| {
-                                ui.heading("SPD");
-                            });
-                            header.col(|ui| {
-                                ui.heading("Ability");
-                            });
-                            header.col(|ui| {
-                                ui.heading("Gender");
-                            });
-                            header.col(|ui| {
-                                ui.heading("Nature");
-                            });
-                            header.col(|ui| {
-                                ui.heading("Item");
-                            });
-                            header.col(|ui| {
-                                ui.heading("Egg Move");
-                            });
-                            header.col(|ui| {
-                                ui.heading("EC");
+                    ui.add_space(5.0);
+                    egui::Grid::new("iv_extra_filter")
+                        .num_columns(2)
+                        .spacing([5.0, 5.0])
+                        .show(ui, |ui| {
+                            ui.label(text::ui("perfect_ivs"));
+                            ui.add(
+                                egui::DragValue::new(&mut self.min_perfect_ivs).clamp_range(0..=6),
+                            )
+                            .on_hover_text(text::ui("perfect_ivs_hint"));
+                            ui.end_row();
+                            ui.label(text::ui("zero_ivs"));
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.zero_atk, text::ui("column_atk"));
+                                ui.checkbox(&mut self.zero_spe, text::ui("column_spe"));
                             });
-                        })
-                        .body(|body| {
-                            body.rows(18.0, self.results.len(), |index, mut row| {
-                                let result = self.results.get(index).unwrap();
-                                row.col(|ui| {
-                                    ui.label(&result.0);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.1);
-                                });
-                                row.col(|ui| {
-                                    ui.label(result.2);
-                                });
-                                row.col(|ui| {
-                                    ui.label(result.3);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.4);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.5);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.6);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.7);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.8);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.9);
-                                });
-                                row.col(|ui| {
-                                    ui.label(result.10);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.11);
-                                });
-                                row.col(|ui| {
-                                    ui.label(result.12);
-                                });
-                                row.col(|ui| {
-                                    ui.label(result.13);
-                                });
-                                row.col(|ui| {
-                                    ui.label(result.14);
-                                });
-                                row.col(|ui| {
-                                    ui.label(&result.15);
+                            ui.end_row();
+                            ui.label(text::ui("hidden_power"));
+                            egui::ComboBox::from_id_source("cmb_hidden_power")
+                                .width(80.0)
+                                .selected_text(match self.hidden_power {
+                                    Some(ty) => text::type_name(ty),
+                                    None => text::ui("any"),
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut self.hidden_power,
+                                        None,
+                                        text::ui("any"),
+                                    );
+                                    for ty in HIDDEN_POWER_TYPES {
+                                        ui.selectable_value(
+                                            &mut self.hidden_power,
+                                            Some(ty),
+                                            text::type_name(ty),
+                                        );
+                                    }
                                 });
-                            });
+                            ui.end_row();
                         });
                 });
+                ui.add_space(10.0);
+                ui.with_layout(egui::Layout::bottom_up(egui::Align::Min), |ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button(text::ui("export_csv")).clicked() {
+                            self.export_results("csv");
+                        }
+                        if ui.button(text::ui("export_json")).clicked() {
+                            self.export_results("json");
+                        }
+                        if ui.button(text::ui("clear_filters")).clicked() {
+                            self.results_view.clear_filters();
+                        }
+                    });
+                    ui.with_layout(egui::Layout::top_down(egui::Align::Min), |ui| {
+                        self.results_view.show(ui, &self.results);
+                    });
+                });
             });
         });
     }
@@ -0,0 +1,186 @@
+use crate::encounters::{spawn_counts, Estimate, SpawnCounts};
+use crate::job::{Job, Steps};
+use crate::search::{SearchRequest, MAX_STATUES};
+use bdsp_ug_generator::personal_table;
+use bdsp_ug_generator::statues::Statue;
+
+/// Statues that share a type with `species`, the only ones that can raise its spawn weight.
+pub fn candidate_statues(catalogue: &[Statue], species: u16) -> Vec<Statue> {
+    let target_types = types(species as usize);
+    catalogue
+        .iter()
+        .filter(|statue| {
+            let statue_types = types(statue.mons_id);
+            target_types.iter().any(|ty| statue_types.contains(ty))
+        })
+        .copied()
+        .collect()
+}
+
+fn types(species: usize) -> [usize; 2] {
+    let personal_info = personal_table::BDSP.get_form_entry(species, 0);
+    [personal_info.get_type_1(), personal_info.get_type_2()]
+}
+
+/// A suggested statue layout and its estimated spawn rate.
+pub struct Suggestion {
+    pub statues: Vec<Statue>,
+    pub rate: Estimate,
+}
+
+/// Spawn rate with the request's own statues, and the suggested layout.
+pub struct Optimized {
+    pub current: Estimate,
+    pub suggestion: Suggestion,
+}
+
+/// Builds a layout for `species` one statue at a time, each time adding whichever candidate
+/// raises the estimated spawn rate the most, until the base is full or no candidate raises
+/// it by more than twice the sampling error of the difference.
+///
+/// This is a greedy search, so it finds a good layout rather than provably the best one.
+/// Every rate is a separate sample, so the work is done one sample per [`Greedy::sample`].
+struct Greedy {
+    request: SearchRequest,
+    species: u16,
+    candidates: Vec<Statue>,
+    /// Counts with the request's own statues, measured by the first step.
+    current: Option<SpawnCounts>,
+    /// Counts of the layout built so far, measured by the second step.
+    best: Option<SpawnCounts>,
+    next_candidate: usize,
+    step_best: Option<(Statue, SpawnCounts)>,
+    evaluated: u32,
+    done: bool,
+}
+
+impl Greedy {
+    fn new(request: SearchRequest, species: u16, candidates: Vec<Statue>) -> Self {
+        Self {
+            request,
+            species,
+            candidates,
+            current: None,
+            best: None,
+            next_candidate: 0,
+            step_best: None,
+            evaluated: 0,
+            done: false,
+        }
+    }
+
+    /// Upper bound on the samples [`Greedy::sample`] takes.
+    fn total(&self) -> u32 {
+        2 + (MAX_STATUES * self.candidates.len()) as u32
+    }
+
+    /// Takes one sample. Returns whether the layout is final.
+    fn sample(&mut self) -> bool {
+        if self.done {
+            return true;
+        }
+        self.evaluated += 1;
+        let best = match (&self.current, &self.best) {
+            (None, _) => {
+                self.current = Some(spawn_counts(&self.request, self.species));
+                self.request.statues.clear();
+                return false;
+            }
+            (_, None) => {
+                self.best = Some(spawn_counts(&self.request, self.species));
+                self.done = self.candidates.is_empty();
+                return self.done;
+            }
+            (_, Some(best)) => best,
+        };
+
+        let candidate = self.candidates[self.next_candidate];
+        self.request.statues.push(candidate);
+        let counts = spawn_counts(&self.request, self.species);
+        self.request.statues.pop();
+        let step_rate = self
+            .step_best
+            .as_ref()
+            .map(|(_, counts)| counts.estimate().rate)
+            .unwrap_or_else(|| best.estimate().rate);
+        if counts.estimate().rate > step_rate {
+            self.step_best = Some((candidate, counts));
+        }
+
+        self.next_candidate += 1;
+        if self.next_candidate == self.candidates.len() {
+            self.next_candidate = 0;
+            match self.step_best.take() {
+                Some((statue, counts)) if best.significantly_below(&counts) => {
+                    self.request.statues.push(statue);
+                    self.best = Some(counts);
+                    self.done = self.request.statues.len() >= MAX_STATUES;
+                }
+                _ => self.done = true,
+            }
+        }
+        self.done
+    }
+
+    fn result(&self) -> Optimized {
+        Optimized {
+            current: self
+                .current
+                .as_ref()
+                .map(SpawnCounts::estimate)
+                .unwrap_or_default(),
+            suggestion: Suggestion {
+                statues: self.request.statues.clone(),
+                rate: self
+                    .best
+                    .as_ref()
+                    .map(SpawnCounts::estimate)
+                    .unwrap_or_default(),
+            },
+        }
+    }
+}
+
+impl Steps for Greedy {
+    /// The result, once the layout is final.
+    type Output = Option<Optimized>;
+
+    fn step(&mut self) -> (Option<Optimized>, bool) {
+        let done = self.sample();
+        (if done { Some(self.result()) } else { None }, done)
+    }
+
+    fn completed(&self) -> u32 {
+        self.evaluated
+    }
+}
+
+/// An optimization running as a [`Job`], one sample per step.
+pub struct OptimizerJob {
+    total: u32,
+    job: Job<Greedy>,
+}
+
+impl OptimizerJob {
+    pub fn start(request: SearchRequest, species: u16, candidates: Vec<Statue>) -> Self {
+        let greedy = Greedy::new(request, species, candidates);
+        Self {
+            total: greedy.total(),
+            job: Job::start(greedy),
+        }
+    }
+
+    /// Returns the result once the optimization has finished.
+    pub fn poll(&mut self) -> Option<Optimized> {
+        self.job.poll().into_iter().flatten().last()
+    }
+
+    /// Fraction of the most samples the optimization could take. It usually stops earlier.
+    pub fn progress(&self) -> f32 {
+        self.job.completed() as f32 / self.total as f32
+    }
+
+    pub fn cancel(&mut self) {
+        self.job.cancel();
+    }
+}
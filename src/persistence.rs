@@ -0,0 +1,209 @@
+use crate::statues::{find_statue, statue_key, SavedStatuePreset};
+use crate::tracker::DEFAULT_RATE;
+use crate::{BDSPUgGeneratorUI, StoryFlag, GENDER_SYMBOLS, HIDDEN_POWER_TYPES, ROOMS};
+use bdsp_ug_generator::statues::StatueConfig;
+use bdsp_ug_generator::{available_pokemon, Version};
+use eframe::Storage;
+use serde::{Deserialize, Serialize};
+
+/// Search inputs saved between sessions through eframe's storage.
+///
+/// Game enums are stored as plain indices so the saved format doesn't depend on the
+/// generator crate's types.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct SavedInputs {
+    s0: String,
+    s1: String,
+    s2: String,
+    s3: String,
+    min_advances: u32,
+    max_advances: u32,
+    delay: u32,
+    min_ivs: [u8; 6],
+    max_ivs: [u8; 6],
+    min_iv_total: u16,
+    max_iv_total: u16,
+    min_perfect_ivs: u8,
+    zero_atk: bool,
+    zero_spe: bool,
+    hidden_power: Option<usize>,
+    version: u8,
+    story_flag: u8,
+    room: usize,
+    diglett_mode: bool,
+    shiny: bool,
+    exclusive: bool,
+    gender: Option<u8>,
+    ability: Option<u8>,
+    egg_move: Option<u16>,
+    natures: [bool; 25],
+    item: Option<u16>,
+    species: Vec<u16>,
+    /// Statues as `(mons_id, rare)` pairs.
+    statues: Vec<(usize, bool)>,
+    advances_per_second: f32,
+}
+
+impl Default for SavedInputs {
+    fn default() -> Self {
+        Self {
+            s0: "".to_string(),
+            s1: "".to_string(),
+            s2: "".to_string(),
+            s3: "".to_string(),
+            min_advances: 0,
+            max_advances: 10000,
+            delay: 0,
+            min_ivs: [0, 0, 0, 0, 0, 0],
+            max_ivs: [31, 31, 31, 31, 31, 31],
+            min_iv_total: 0,
+            max_iv_total: 186,
+            min_perfect_ivs: 0,
+            zero_atk: false,
+            zero_spe: false,
+            hidden_power: None,
+            version: 0,
+            story_flag: StoryFlag::default() as u8,
+            room: 0,
+            diglett_mode: false,
+            shiny: false,
+            exclusive: false,
+            gender: None,
+            ability: None,
+            egg_move: None,
+            natures: [false; 25],
+            item: None,
+            species: vec![],
+            statues: vec![],
+            advances_per_second: DEFAULT_RATE,
+        }
+    }
+}
+
+impl SavedInputs {
+    pub(crate) fn from_ui(ui: &BDSPUgGeneratorUI) -> Self {
+        Self {
+            s0: ui.s0.clone(),
+            s1: ui.s1.clone(),
+            s2: ui.s2.clone(),
+            s3: ui.s3.clone(),
+            min_advances: ui.min_advances,
+            max_advances: ui.max_advances,
+            delay: ui.delay,
+            min_ivs: ui.min_ivs,
+            max_ivs: ui.max_ivs,
+            min_iv_total: ui.min_iv_total,
+            max_iv_total: ui.max_iv_total,
+            min_perfect_ivs: ui.min_perfect_ivs,
+            zero_atk: ui.zero_atk,
+            zero_spe: ui.zero_spe,
+            hidden_power: ui.hidden_power,
+            version: match ui.version {
+                Version::BD => 0,
+                Version::SP => 1,
+            },
+            story_flag: ui.story_flag as u8,
+            room: ROOMS
+                .iter()
+                .position(|room| *room == ui.room)
+                .unwrap_or_default(),
+            diglett_mode: ui.diglett_mode,
+            shiny: ui.shiny,
+            exclusive: ui.exclusive,
+            gender: ui.gender,
+            ability: ui.ability,
+            egg_move: ui.egg_move,
+            natures: ui.natures,
+            item: ui.item,
+            species: ui.species.clone(),
+            statues: ui.statue_config.statues.iter().map(statue_key).collect(),
+            advances_per_second: ui.tracker.rate,
+        }
+    }
+
+    /// Applies the saved inputs. They may come from a crafted share link, so values the UI
+    /// couldn't have produced are dropped or clamped instead of trusted.
+    pub(crate) fn apply(self, ui: &mut BDSPUgGeneratorUI) {
+        ui.s0 = self.s0;
+        ui.s1 = self.s1;
+        ui.s2 = self.s2;
+        ui.s3 = self.s3;
+        ui.min_advances = self.min_advances;
+        ui.max_advances = self.max_advances;
+        ui.delay = self.delay;
+        ui.min_ivs = self.min_ivs.map(|iv| iv.min(31));
+        ui.max_ivs = self.max_ivs.map(|iv| iv.min(31));
+        ui.min_iv_total = self.min_iv_total.min(186);
+        ui.max_iv_total = self.max_iv_total.min(186);
+        ui.min_perfect_ivs = self.min_perfect_ivs.min(6);
+        ui.zero_atk = self.zero_atk;
+        ui.zero_spe = self.zero_spe;
+        // Normal isn't a hidden power type.
+        ui.hidden_power = self
+            .hidden_power
+            .filter(|ty| HIDDEN_POWER_TYPES.contains(ty));
+        ui.version = if self.version == 1 {
+            Version::SP
+        } else {
+            Version::BD
+        };
+        ui.story_flag = StoryFlag::ALL
+            .into_iter()
+            .find(|&flag| flag as u8 == self.story_flag)
+            .unwrap_or_default();
+        ui.room = ROOMS.get(self.room).copied().unwrap_or(ROOMS[0]);
+        ui.diglett_mode = self.diglett_mode;
+        ui.shiny = self.shiny;
+        ui.exclusive = self.exclusive;
+        ui.gender = self
+            .gender
+            .filter(|&gender| (gender as usize) < GENDER_SYMBOLS.len());
+        ui.ability = self.ability.filter(|&ability| ability <= 1);
+        ui.egg_move = self.egg_move;
+        ui.natures = self.natures;
+        ui.item = self.item;
+        ui.available_pokemon = available_pokemon(ui.version, ui.story_flag as u8, ui.room);
+        ui.species = self.species;
+        let available = &ui.available_pokemon;
+        ui.species.retain(|species| available.contains(species));
+        ui.species_changed();
+        ui.statue_config = StatueConfig::default();
+        for key in self.statues {
+            if let Some(statue) = find_statue(&ui.statue_data, key) {
+                ui.statue_config.add_statue(statue);
+            }
+        }
+        ui.tracker.rate = if self.advances_per_second.is_finite() {
+            self.advances_per_second.clamp(0.1, 10000.0)
+        } else {
+            DEFAULT_RATE
+        };
+    }
+}
+
+/// Statue presets are stored apart from the search inputs so sharing a search doesn't carry
+/// them along.
+const PRESETS_KEY: &str = "statue_presets";
+
+pub(crate) fn load(storage: &dyn Storage, ui: &mut BDSPUgGeneratorUI) {
+    if let Some(saved) = eframe::get_value::<SavedInputs>(storage, eframe::APP_KEY) {
+        saved.apply(ui);
+    }
+    if let Some(presets) = eframe::get_value::<Vec<SavedStatuePreset>>(storage, PRESETS_KEY) {
+        ui.statue_presets = presets
+            .iter()
+            .map(|preset| preset.to_preset(&ui.statue_data))
+            .collect();
+    }
+}
+
+pub(crate) fn save(storage: &mut dyn Storage, ui: &BDSPUgGeneratorUI) {
+    eframe::set_value(storage, eframe::APP_KEY, &SavedInputs::from_ui(ui));
+    let presets = ui
+        .statue_presets
+        .iter()
+        .map(SavedStatuePreset::from_preset)
+        .collect::<Vec<SavedStatuePreset>>();
+    eframe::set_value(storage, PRESETS_KEY, &presets);
+}
@@ -0,0 +1,642 @@
+use crate::{text, StoryFlag, GENDER_SYMBOLS};
+use bdsp_ug_generator::statues::{Statue, StatueConfig};
+use bdsp_ug_generator::xorshift::XorShift;
+use bdsp_ug_generator::{personal_table, run_results, Filter, RoomType, Version};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// A single spawn from a search, kept as raw game data and formatted only for display.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ResultRow {
+    pub advance: u32,
+    pub pid: u32,
+    pub ec: u32,
+    pub ivs: [u8; 6],
+    pub species: u16,
+    pub nature: u8,
+    /// Ability slot, 0 for the first ability and 1 for the second.
+    pub ability: u8,
+    pub item: u16,
+    pub egg_move: Option<u16>,
+    pub shiny: bool,
+    pub gender: u8,
+    pub rare: bool,
+}
+
+impl ResultRow {
+    pub fn pid_str(&self) -> String {
+        format!("{:X}", self.pid)
+    }
+
+    pub fn ec_str(&self) -> String {
+        format!("{:X}", self.ec)
+    }
+
+    pub fn species_str(&self) -> &'static str {
+        text::species(self.species as usize)
+    }
+
+    pub fn shiny_str(&self) -> &'static str {
+        text::ui(if self.shiny {
+            "shiny_shiny"
+        } else {
+            "shiny_none"
+        })
+    }
+
+    /// Returns the ability id for the spawn's species and ability slot.
+    pub fn ability_id(&self) -> usize {
+        let personal_info = personal_table::BDSP.get_form_entry(self.species as usize, 0);
+        if self.ability == 0 {
+            personal_info.get_ability_1()
+        } else {
+            personal_info.get_ability_2()
+        }
+    }
+
+    pub fn ability_str(&self) -> &'static str {
+        text::ability(self.ability_id())
+    }
+
+    pub fn gender_str(&self) -> &'static str {
+        GENDER_SYMBOLS[self.gender as usize]
+    }
+
+    pub fn nature_str(&self) -> &'static str {
+        text::nature(self.nature as usize)
+    }
+
+    pub fn item_str(&self) -> &'static str {
+        text::item(self.item as usize)
+    }
+
+    pub fn egg_move_str(&self) -> &'static str {
+        text::move_name(self.egg_move.unwrap_or(0) as usize)
+    }
+
+    pub fn iv_total(&self) -> u16 {
+        self.ivs.iter().map(|&iv| iv as u16).sum()
+    }
+
+    /// Index into `TYPES_EN` of the spawn's hidden power type, always in `HIDDEN_POWER_TYPES`.
+    pub fn hidden_power_type(&self) -> usize {
+        // Hidden power reads the IVs in HP, Atk, Def, Spe, SpA, SpD order.
+        let bits = [0, 1, 2, 5, 3, 4]
+            .into_iter()
+            .enumerate()
+            .map(|(bit, stat)| ((self.ivs[stat] & 1) as usize) << bit)
+            .sum::<usize>();
+        // Fighting through Dark, skipping Normal.
+        1 + bits * 15 / 63
+    }
+
+    pub fn hidden_power_str(&self) -> &'static str {
+        text::type_name(self.hidden_power_type())
+    }
+
+    /// Returns the displayed text for `column`.
+    pub fn text(&self, column: ResultColumn) -> String {
+        match column {
+            ResultColumn::Advance => self.advance.to_string(),
+            ResultColumn::Pid => self.pid_str(),
+            ResultColumn::Species => self.species_str().to_string(),
+            ResultColumn::Shiny => self.shiny_str().to_string(),
+            ResultColumn::Hp => self.ivs[0].to_string(),
+            ResultColumn::Atk => self.ivs[1].to_string(),
+            ResultColumn::Def => self.ivs[2].to_string(),
+            ResultColumn::Spa => self.ivs[3].to_string(),
+            ResultColumn::Spd => self.ivs[4].to_string(),
+            ResultColumn::Spe => self.ivs[5].to_string(),
+            ResultColumn::Ability => self.ability_str().to_string(),
+            ResultColumn::Gender => self.gender_str().to_string(),
+            ResultColumn::Nature => self.nature_str().to_string(),
+            ResultColumn::Item => self.item_str().to_string(),
+            ResultColumn::EggMove => self.egg_move_str().to_string(),
+            ResultColumn::Ec => self.ec_str(),
+        }
+    }
+
+    /// Returns the raw number behind `column`, or `None` for columns that only make sense as text.
+    pub fn number(&self, column: ResultColumn) -> Option<u32> {
+        match column {
+            ResultColumn::Advance => Some(self.advance),
+            ResultColumn::Pid => Some(self.pid),
+            ResultColumn::Shiny => Some(self.shiny as u32),
+            ResultColumn::Hp => Some(self.ivs[0] as u32),
+            ResultColumn::Atk => Some(self.ivs[1] as u32),
+            ResultColumn::Def => Some(self.ivs[2] as u32),
+            ResultColumn::Spa => Some(self.ivs[3] as u32),
+            ResultColumn::Spd => Some(self.ivs[4] as u32),
+            ResultColumn::Spe => Some(self.ivs[5] as u32),
+            ResultColumn::Ec => Some(self.ec),
+            _ => None,
+        }
+    }
+
+    /// Orders two rows by `column`, numerically where the column has a raw number.
+    pub fn compare(&self, other: &Self, column: ResultColumn) -> Ordering {
+        match (self.number(column), other.number(column)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            _ => self.text(column).cmp(&other.text(column)),
+        }
+    }
+}
+
+/// The columns shown for each [`ResultRow`], in display order.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResultColumn {
+    Advance,
+    Pid,
+    Species,
+    Shiny,
+    Hp,
+    Atk,
+    Def,
+    Spa,
+    Spd,
+    Spe,
+    Ability,
+    Gender,
+    Nature,
+    Item,
+    EggMove,
+    Ec,
+}
+
+impl ResultColumn {
+    pub const ALL: [ResultColumn; 16] = [
+        ResultColumn::Advance,
+        ResultColumn::Pid,
+        ResultColumn::Species,
+        ResultColumn::Shiny,
+        ResultColumn::Hp,
+        ResultColumn::Atk,
+        ResultColumn::Def,
+        ResultColumn::Spa,
+        ResultColumn::Spd,
+        ResultColumn::Spe,
+        ResultColumn::Ability,
+        ResultColumn::Gender,
+        ResultColumn::Nature,
+        ResultColumn::Item,
+        ResultColumn::EggMove,
+        ResultColumn::Ec,
+    ];
+
+    pub fn header(self) -> &'static str {
+        text::ui(match self {
+            ResultColumn::Advance => "column_advance",
+            ResultColumn::Pid => "column_pid",
+            ResultColumn::Species => "column_species",
+            ResultColumn::Shiny => "column_shiny",
+            ResultColumn::Hp => "column_hp",
+            ResultColumn::Atk => "column_atk",
+            ResultColumn::Def => "column_def",
+            ResultColumn::Spa => "column_spa",
+            ResultColumn::Spd => "column_spd",
+            ResultColumn::Spe => "column_spe",
+            ResultColumn::Ability => "column_ability",
+            ResultColumn::Gender => "column_gender",
+            ResultColumn::Nature => "column_nature",
+            ResultColumn::Item => "column_item",
+            ResultColumn::EggMove => "column_egg_move",
+            ResultColumn::Ec => "column_ec",
+        })
+    }
+}
+
+/// Most statues a fully expanded secret base can display.
+pub const MAX_STATUES: usize = 18;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SearchError {
+    /// The seed word at the given index (0 to 3) is not a valid 32-bit hex value.
+    InvalidSeed(usize),
+    /// More statues were given than a secret base can hold.
+    TooManyStatues(usize),
+}
+
+impl Display for SearchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SearchError::InvalidSeed(index) => {
+                write!(f, "{}", text::ui_fmt("error_invalid_seed", &[index]))
+            }
+            SearchError::TooManyStatues(count) => write!(
+                f,
+                "{}",
+                text::ui_fmt("error_too_many_statues", &[count, &MAX_STATUES])
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SearchError {}
+
+fn strip_hex_prefix(s: &str) -> &str {
+    s.strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s)
+}
+
+/// Parses a single seed word of up to 8 hex digits, with or without a `0x` prefix.
+pub fn parse_seed(s: &str) -> Option<u32> {
+    let s = strip_hex_prefix(s.trim());
+    if s.is_empty() || s.len() > 8 {
+        return None;
+    }
+    u32::from_str_radix(s, 16).ok()
+}
+
+/// Parses the four hex seed words into an `XorShift` state.
+pub fn parse_state(s0: &str, s1: &str, s2: &str, s3: &str) -> Result<[u32; 4], SearchError> {
+    let mut state = [0; 4];
+    for (i, s) in [s0, s1, s2, s3].into_iter().enumerate() {
+        state[i] = parse_seed(s).ok_or(SearchError::InvalidSeed(i))?;
+    }
+    Ok(state)
+}
+
+/// Splits a whole pasted state into its four seed words.
+///
+/// Accepts 32 hex digits in one piece, four 32-bit words (`s0 s1 s2 s3`) or two 64-bit
+/// halves where the first half holds `s0` in its upper 32 bits and `s1` in its lower 32 bits.
+/// At least one half must be longer than 8 digits so two short words aren't mistaken for it.
+/// Labels ending in `:` or `=`, such as `S[0]:` or `Seed 0:`, and separators like commas are
+/// ignored.
+pub fn split_state(text: &str) -> Option<[u32; 4]> {
+    let tokens = text
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';'))
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<&str>>();
+    let mut words = vec![];
+    for (i, token) in tokens.iter().enumerate() {
+        // A label spaced apart from its `:`, as in `Seed 0 : ...`.
+        let is_label = tokens
+            .get(i + 1)
+            .map(|next| next.starts_with([':', '=']))
+            .unwrap_or(false);
+        if is_label {
+            continue;
+        }
+        // Only what follows a label's `:` or `=` can be a value.
+        let value = match token.rfind([':', '=']) {
+            Some(index) => &token[index + 1..],
+            None => token,
+        };
+        let value = strip_hex_prefix(value);
+        // Skips the empty rest of labels like `0:` and label words like `Seed`.
+        if !value.is_empty() && value.chars().all(|c| c.is_ascii_hexdigit()) {
+            words.push(value);
+        }
+    }
+
+    match words.as_slice() {
+        [word] if word.len() == 32 => {
+            let mut state = [0; 4];
+            for (i, seed) in state.iter_mut().enumerate() {
+                *seed = u32::from_str_radix(&word[i * 8..(i + 1) * 8], 16).ok()?;
+            }
+            Some(state)
+        }
+        [s0, s1, s2, s3] if words.iter().all(|word| word.len() <= 8) => Some([
+            parse_seed(s0)?,
+            parse_seed(s1)?,
+            parse_seed(s2)?,
+            parse_seed(s3)?,
+        ]),
+        [seed0, seed1]
+            if seed0.len() <= 16 && seed1.len() <= 16 && seed0.len().max(seed1.len()) > 8 =>
+        {
+            let seed0 = u64::from_str_radix(seed0, 16).ok()?;
+            let seed1 = u64::from_str_radix(seed1, 16).ok()?;
+            Some([
+                (seed0 >> 32) as u32,
+                seed0 as u32,
+                (seed1 >> 32) as u32,
+                seed1 as u32,
+            ])
+        }
+        _ => None,
+    }
+}
+
+/// Owned copy of the `Filter` fields so a request can be cloned and reused.
+#[derive(Clone)]
+pub struct SearchFilter {
+    pub shiny: bool,
+    /// Species to match, any species when empty.
+    pub species: Vec<u16>,
+    pub min_ivs: [u8; 6],
+    pub max_ivs: [u8; 6],
+    pub ability: Option<u8>,
+    pub nature: Option<Vec<u8>>,
+    pub item: Option<u16>,
+    pub egg_move: Option<u16>,
+    pub gender: Option<u8>,
+    pub exclusive: bool,
+    /// Minimum number of IVs at 31.
+    pub min_perfect_ivs: u8,
+    pub min_iv_total: u16,
+    pub max_iv_total: u16,
+    pub zero_atk: bool,
+    pub zero_spe: bool,
+    /// Index into `TYPES_EN` of the hidden power type to match.
+    pub hidden_power: Option<usize>,
+}
+
+impl Default for SearchFilter {
+    fn default() -> Self {
+        Self {
+            shiny: false,
+            species: vec![],
+            min_ivs: [0, 0, 0, 0, 0, 0],
+            max_ivs: [31, 31, 31, 31, 31, 31],
+            ability: None,
+            nature: None,
+            item: None,
+            egg_move: None,
+            gender: None,
+            exclusive: false,
+            min_perfect_ivs: 0,
+            min_iv_total: 0,
+            max_iv_total: 186,
+            zero_atk: false,
+            zero_spe: false,
+            hidden_power: None,
+        }
+    }
+}
+
+impl SearchFilter {
+    /// Builds the generator's `Filter`. It only supports a single species, so when several
+    /// are selected the species check is left to [`SearchFilter::matches_species`].
+    pub fn to_filter(&self) -> Filter {
+        Filter {
+            shiny: self.shiny,
+            species: match self.species.as_slice() {
+                [species] => Some(*species),
+                _ => None,
+            },
+            min_ivs: self.min_ivs,
+            max_ivs: self.max_ivs,
+            ability: self.ability,
+            nature: self.nature.clone(),
+            item: self.item,
+            egg_move: self.egg_move,
+            gender: self.gender,
+            exclusive: self.exclusive,
+        }
+    }
+}
+
+impl SearchFilter {
+    pub fn matches_species(&self, species: u16) -> bool {
+        self.species.is_empty() || self.species.contains(&species)
+    }
+
+    /// Checks the conditions the generator's `Filter` has no fields for.
+    pub fn matches_row(&self, row: &ResultRow) -> bool {
+        let perfect = row.ivs.iter().filter(|&&iv| iv == 31).count();
+        let total = row.iv_total();
+        perfect >= self.min_perfect_ivs as usize
+            && (self.min_iv_total..=self.max_iv_total).contains(&total)
+            && (!self.zero_atk || row.ivs[1] == 0)
+            && (!self.zero_spe || row.ivs[5] == 0)
+            && self.hidden_power.unwrap_or(row.hidden_power_type()) == row.hidden_power_type()
+    }
+}
+
+/// Everything needed to run an underground search.
+#[derive(Clone)]
+pub struct SearchRequest {
+    pub state: [u32; 4],
+    pub min_advances: u32,
+    pub max_advances: u32,
+    pub delay: u32,
+    pub version: Version,
+    pub story_flag: StoryFlag,
+    pub room: RoomType,
+    pub diglett_mode: bool,
+    pub statues: Vec<Statue>,
+    pub filter: SearchFilter,
+}
+
+impl Default for SearchRequest {
+    fn default() -> Self {
+        Self {
+            state: [0; 4],
+            min_advances: 0,
+            max_advances: 10000,
+            delay: 0,
+            version: Version::BD,
+            story_flag: StoryFlag::default(),
+            room: RoomType::SpaciousCave,
+            diglett_mode: false,
+            statues: vec![],
+            filter: SearchFilter::default(),
+        }
+    }
+}
+
+impl SearchRequest {
+    /// Rejects requests the game couldn't produce.
+    pub fn validate(&self) -> Result<(), SearchError> {
+        if self.statues.len() > MAX_STATUES {
+            return Err(SearchError::TooManyStatues(self.statues.len()));
+        }
+        Ok(())
+    }
+
+    pub fn statue_config(&self) -> StatueConfig {
+        let mut statue_config = StatueConfig::default();
+        for &statue in &self.statues {
+            statue_config.add_statue(statue);
+        }
+        statue_config
+    }
+
+    /// Returns the generator advanced to the first searched advance.
+    pub fn rng(&self) -> XorShift {
+        self.rng_at(0)
+    }
+
+    /// Returns the generator advanced `offset` advances past the first searched advance.
+    pub fn rng_at(&self, offset: u32) -> XorShift {
+        let start = self.min_advances as usize + offset as usize;
+        let mut rng = XorShift::from_state(self.state);
+        if start < 4096 {
+            rng.advance(start + self.delay as usize);
+        } else {
+            rng.jump(start + self.delay as usize);
+        }
+        rng
+    }
+}
+
+pub struct SearchOutcome {
+    pub rows: Vec<ResultRow>,
+}
+
+pub fn search(request: &SearchRequest) -> SearchOutcome {
+    SearchOutcome {
+        rows: search_range(request, 0, request.max_advances, true),
+    }
+}
+
+/// Searches `len` advances starting `offset` advances after the request's first advance.
+///
+/// `run_results` generates advances `0..=len`. Unless `last` is set the final one is dropped,
+/// since it is the first advance of the next range. Splitting `0..max_advances` into
+/// consecutive ranges therefore gives the same rows as a single [`search`], as long as only
+/// the final range has `last` set.
+pub fn search_range(request: &SearchRequest, offset: u32, len: u32, last: bool) -> Vec<ResultRow> {
+    let results = run_results(
+        len,
+        request.rng_at(offset),
+        request.version,
+        request.story_flag as u8,
+        request.room,
+        request.filter.to_filter(),
+        request.diglett_mode,
+        &request.statue_config(),
+    );
+
+    let mut count = 0;
+    for result in results.iter() {
+        count += result.regular_pokemon.len();
+        if result.rare_pokemon.is_some() {
+            count += 1;
+        }
+    }
+
+    let mut rows = Vec::with_capacity(count);
+    for result in results {
+        if !last && result.advance >= len {
+            continue;
+        }
+        let advance = result.advance + offset + request.min_advances;
+        let regular = result
+            .regular_pokemon
+            .iter()
+            .map(|pokemon| (pokemon, false));
+        let rare = result.rare_pokemon.iter().map(|pokemon| (pokemon, true));
+        for (pokemon, rare) in regular.chain(rare) {
+            if !request.filter.matches_species(pokemon.species) {
+                continue;
+            }
+            let row = ResultRow {
+                advance,
+                pid: pokemon.pid,
+                ec: pokemon.ec,
+                ivs: pokemon.ivs,
+                species: pokemon.species,
+                nature: pokemon.nature,
+                ability: pokemon.ability,
+                item: pokemon.item,
+                egg_move: pokemon.egg_move,
+                shiny: pokemon.shiny,
+                gender: pokemon.gender,
+                rare,
+            };
+            if request.filter.matches_row(&row) {
+                rows.push(row);
+            }
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATE: [u32; 4] = [0x1234ABCD, 0x00000001, 0xDEADBEEF, 0x0F0F0F0F];
+
+    fn row_with_ivs(ivs: [u8; 6]) -> ResultRow {
+        ResultRow {
+            ivs,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hidden_power_type_covers_fighting_to_dark() {
+        assert_eq!(row_with_ivs([0; 6]).hidden_power_type(), 1);
+        assert_eq!(row_with_ivs([30; 6]).hidden_power_type(), 1);
+        assert_eq!(row_with_ivs([31; 6]).hidden_power_type(), 16);
+        assert_eq!(row_with_ivs([1; 6]).hidden_power_type(), 16);
+        assert_eq!(
+            crate::TYPES_EN[row_with_ivs([0; 6]).hidden_power_type()],
+            "Fighting"
+        );
+        assert_eq!(
+            crate::TYPES_EN[row_with_ivs([31; 6]).hidden_power_type()],
+            "Dark"
+        );
+    }
+
+    #[test]
+    fn split_state_accepts_32_digits() {
+        assert_eq!(split_state("1234ABCD00000001DEADBEEF0F0F0F0F"), Some(STATE));
+        assert_eq!(
+            split_state("0x1234abcd00000001deadbeef0f0f0f0f"),
+            Some(STATE)
+        );
+    }
+
+    #[test]
+    fn split_state_accepts_four_words() {
+        assert_eq!(split_state("1234ABCD 1 DEADBEEF F0F0F0F"), Some(STATE));
+        assert_eq!(
+            split_state("0x1234ABCD, 0x00000001, 0xDEADBEEF, 0x0F0F0F0F"),
+            Some(STATE)
+        );
+        assert_eq!(
+            split_state("S[0]: 1234ABCD\nS[1]: 00000001\nS[2]: DEADBEEF\nS[3]: 0F0F0F0F"),
+            Some(STATE)
+        );
+        assert_eq!(
+            split_state("s0=1234ABCD s1=00000001 s2=DEADBEEF s3=0F0F0F0F"),
+            Some(STATE)
+        );
+    }
+
+    #[test]
+    fn split_state_accepts_two_halves() {
+        assert_eq!(
+            split_state("1234ABCD00000001 DEADBEEF0F0F0F0F"),
+            Some(STATE)
+        );
+        assert_eq!(
+            split_state("Seed 0: 1234ABCD00000001 Seed 1: DEADBEEF0F0F0F0F"),
+            Some(STATE)
+        );
+        assert_eq!(
+            split_state("Seed 0 : 1234ABCD00000001, Seed 1 : DEADBEEF0F0F0F0F"),
+            Some(STATE)
+        );
+        // Only one half needs to be longer than a single word.
+        assert_eq!(
+            split_state("1234ABCD00000001 F0F0F0F"),
+            Some([0x1234ABCD, 0x00000001, 0, 0x0F0F0F0F])
+        );
+    }
+
+    #[test]
+    fn split_state_rejects_other_input() {
+        assert_eq!(split_state(""), None);
+        // Two short words are ambiguous, they could be half a state.
+        assert_eq!(split_state("1234ABCD DEADBEEF"), None);
+        assert_eq!(split_state("1234ABCD 1 DEADBEEF"), None);
+        assert_eq!(split_state("1234ABCD 1 DEADBEEF F0F0F0F 5"), None);
+        // 31 and 33 digits.
+        assert_eq!(split_state("1234ABCD00000001DEADBEEF0F0F0F0"), None);
+        assert_eq!(split_state("1234ABCD00000001DEADBEEF0F0F0F0F0"), None);
+        // A word longer than 8 digits among four.
+        assert_eq!(split_state("1234ABCD0 1 DEADBEEF F0F0F0F"), None);
+        // A half longer than 16 digits.
+        assert_eq!(split_state("1234ABCD000000010 DEADBEEF"), None);
+    }
+}
@@ -0,0 +1,22 @@
+use crate::persistence::SavedInputs;
+
+/// Prefix of the URL fragment holding a shared search.
+const HASH_PREFIX: &str = "#search=";
+
+/// Reads a search shared through [`link`] from the page's URL fragment.
+pub(crate) fn decode(hash: &str) -> Option<SavedInputs> {
+    let encoded = hash.strip_prefix(HASH_PREFIX)?;
+    let json = String::from(js_sys::decode_uri_component(encoded).ok()?);
+    serde_json::from_str(&json).ok()
+}
+
+/// Stores the search inputs in the page's URL fragment and returns the full link.
+pub(crate) fn link(inputs: &SavedInputs) -> Option<String> {
+    let json = serde_json::to_string(inputs).ok()?;
+    let encoded = String::from(js_sys::encode_uri_component(&json));
+    let location = web_sys::window()?.location();
+    location
+        .set_hash(&format!("{}{}", HASH_PREFIX, encoded))
+        .ok()?;
+    location.href().ok()
+}
@@ -0,0 +1,435 @@
+use crate::encounters::Estimate;
+use crate::optimizer::{self, OptimizerJob};
+use crate::search::{SearchRequest, MAX_STATUES};
+use crate::{text, BDSPUgGeneratorUI};
+use bdsp_ug_generator::personal_table;
+use bdsp_ug_generator::statues::Statue;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// A named statue layout the user can load back into the statue config.
+#[derive(Clone)]
+pub(crate) struct StatuePreset {
+    pub(crate) name: String,
+    pub(crate) statues: Vec<Statue>,
+}
+
+/// Saved form of a [`StatuePreset`], with statues stored by [`statue_key`].
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SavedStatuePreset {
+    name: String,
+    statues: Vec<(usize, bool)>,
+}
+
+impl SavedStatuePreset {
+    pub(crate) fn from_preset(preset: &StatuePreset) -> Self {
+        Self {
+            name: preset.name.clone(),
+            statues: preset.statues.iter().map(statue_key).collect(),
+        }
+    }
+
+    pub(crate) fn to_preset(&self, statue_data: &[(String, Statue)]) -> StatuePreset {
+        StatuePreset {
+            name: self.name.clone(),
+            statues: self
+                .statues
+                .iter()
+                .filter_map(|&key| find_statue(statue_data, key))
+                .collect(),
+        }
+    }
+}
+
+/// Identifies a statue by its species and whether it is the rare version.
+pub(crate) fn statue_key(statue: &Statue) -> (usize, bool) {
+    (statue.mons_id, statue.rarity != 1)
+}
+
+pub(crate) fn find_statue(statue_data: &[(String, Statue)], key: (usize, bool)) -> Option<Statue> {
+    statue_data
+        .iter()
+        .find(|(_, statue)| statue_key(statue) == key)
+        .map(|(_, statue)| *statue)
+}
+
+/// Pairs each statue with its display name, sorted by name.
+pub(crate) fn sorted_statue_data(statues: Vec<Statue>) -> Vec<(String, Statue)> {
+    let mut statue_data = statues
+        .into_iter()
+        .map(|statue| (statue_name(&statue), statue))
+        .collect::<Vec<(String, Statue)>>();
+    statue_data.sort_by(|s1, s2| s1.0.cmp(&s2.0));
+    statue_data
+}
+
+pub(crate) fn statue_name(statue: &Statue) -> String {
+    if statue.rarity == 1 {
+        text::species(statue.mons_id).to_string()
+    } else {
+        text::ui_fmt("rare_statue", &[&text::species(statue.mons_id)])
+    }
+}
+
+/// Statue count and rarity points per type for the given statues.
+///
+/// A statue counts towards both types of its species; rare statues carry more rarity points.
+pub(crate) fn type_boosts(statues: &[Statue]) -> [(usize, u32); 18] {
+    let mut boosts = [(0, 0); 18];
+    for statue in statues {
+        let personal_info = personal_table::BDSP.get_form_entry(statue.mons_id, 0);
+        let mut types = vec![personal_info.get_type_1()];
+        if personal_info.get_type_2() != types[0] {
+            types.push(personal_info.get_type_2());
+        }
+        for ty in types {
+            if let Some((count, points)) = boosts.get_mut(ty) {
+                *count += 1;
+                *points += statue.rarity as u32;
+            }
+        }
+    }
+    boosts
+}
+
+impl BDSPUgGeneratorUI {
+    pub(crate) fn show_statue_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(text::ui("statue_config"))
+            .id(egui::Id::new("statue_window"))
+            .show(ctx, |ui| {
+                egui::ComboBox::new("statues", "")
+                    .selected_text(if let Some(index) = self.selected_statue.as_ref() {
+                        &self.statue_data[*index].0
+                    } else {
+                        text::ui("none")
+                    })
+                    .width(155.0)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.selected_statue, None, text::ui("none"));
+                        self.statue_data
+                            .iter()
+                            .enumerate()
+                            .for_each(|(index, (name, _))| {
+                                ui.selectable_value(
+                                    &mut self.selected_statue,
+                                    Some(index),
+                                    name.as_str(),
+                                );
+                            });
+                    });
+                let full = self.statue_config.statues.len() >= MAX_STATUES;
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!full, egui::Button::new(text::ui("add")))
+                        .on_disabled_hover_text(text::ui_fmt("statue_limit_hint", &[&MAX_STATUES]))
+                        .clicked()
+                    {
+                        if let Some(index) = self.selected_statue.as_ref() {
+                            self.statue_config.add_statue(self.statue_data[*index].1);
+                        }
+                    }
+
+                    if ui.button(text::ui("remove_last")).clicked() {
+                        self.statue_config.statues.pop();
+                    }
+
+                    if ui.button(text::ui("close")).clicked() {
+                        self.show_statues = false;
+                    }
+                });
+
+                ui.add_space(10.0);
+                ui.heading(text::ui("statues"));
+                let count = self.statue_config.statues.len();
+                if count > MAX_STATUES {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        text::ui_fmt(
+                            "statue_count_over",
+                            &[&count, &MAX_STATUES, &(count - MAX_STATUES)],
+                        ),
+                    );
+                } else {
+                    ui.label(text::ui_fmt("statue_count", &[&count, &MAX_STATUES]));
+                }
+
+                let mut remove = None;
+                let mut swap = None;
+                egui::ScrollArea::vertical()
+                    .id_source("statue_list")
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("statue_grid")
+                            .num_columns(4)
+                            .spacing([5.0, 2.0])
+                            .show(ui, |ui| {
+                                for (index, statue) in self.statue_config.statues.iter().enumerate()
+                                {
+                                    ui.label(statue_name(statue));
+                                    if ui.add_enabled(index > 0, egui::Button::new("▲")).clicked()
+                                    {
+                                        swap = Some((index, index - 1));
+                                    }
+                                    if ui
+                                        .add_enabled(index + 1 < count, egui::Button::new("▼"))
+                                        .clicked()
+                                    {
+                                        swap = Some((index, index + 1));
+                                    }
+                                    if ui.button(text::ui("remove")).clicked() {
+                                        remove = Some(index);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                if let Some((a, b)) = swap {
+                    self.statue_config.statues.swap(a, b);
+                }
+                if let Some(index) = remove {
+                    self.statue_config.statues.remove(index);
+                }
+
+                ui.add_space(10.0);
+                ui.heading(text::ui("type_boosts"));
+                egui::Grid::new("statue_type_boosts")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(text::ui("type"));
+                        ui.strong(text::ui("statues"));
+                        ui.strong(text::ui("rarity_points"));
+                        ui.end_row();
+                        for (ty, (count, points)) in type_boosts(&self.statue_config.statues)
+                            .into_iter()
+                            .enumerate()
+                            .filter(|(_, (count, _))| *count > 0)
+                        {
+                            ui.label(text::type_name(ty));
+                            ui.label(count.to_string());
+                            ui.label(points.to_string());
+                            ui.end_row();
+                        }
+                    });
+
+                ui.add_space(10.0);
+                ui.heading(text::ui("optimizer"));
+                self.show_statue_optimizer(ui);
+
+                ui.add_space(10.0);
+                ui.heading(text::ui("presets"));
+                self.show_statue_presets(ui);
+            });
+    }
+
+    fn show_statue_optimizer(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(text::ui("target"));
+            egui::ComboBox::from_id_source("cmb_optimizer_species")
+                .width(150.0)
+                .selected_text(match self.optimizer_species {
+                    Some(species) => text::species(species as usize),
+                    None => text::ui("none"),
+                })
+                .show_ui(ui, |ui| {
+                    for &species in &self.available_pokemon {
+                        ui.selectable_value(
+                            &mut self.optimizer_species,
+                            Some(species),
+                            text::species(species as usize),
+                        );
+                    }
+                });
+
+            if let Some(job) = self.optimizer_job.as_mut() {
+                if ui.button(text::ui("cancel")).clicked() {
+                    job.cancel();
+                    self.optimizer_job = None;
+                } else {
+                    ui.add(egui::ProgressBar::new(job.progress()).desired_width(100.0));
+                    match job.poll() {
+                        Some(result) => {
+                            self.optimizer_result = Some(result);
+                            self.optimizer_job = None;
+                        }
+                        None => ui.ctx().request_repaint(),
+                    }
+                }
+            } else if ui
+                .add_enabled(
+                    self.optimizer_species.is_some(),
+                    egui::Button::new(text::ui("optimize")),
+                )
+                .on_hover_text(text::ui("optimize_hint"))
+                .clicked()
+            {
+                if let Some(species) = self.optimizer_species {
+                    let request = SearchRequest {
+                        version: self.version,
+                        story_flag: self.story_flag,
+                        room: self.room,
+                        diglett_mode: self.diglett_mode,
+                        statues: self.statue_config.statues.clone(),
+                        ..Default::default()
+                    };
+                    let catalogue = self
+                        .statue_data
+                        .iter()
+                        .map(|(_, statue)| *statue)
+                        .collect::<Vec<Statue>>();
+                    let candidates = optimizer::candidate_statues(&catalogue, species);
+                    self.optimizer_result = None;
+                    self.optimizer_job = Some(OptimizerJob::start(request, species, candidates));
+                }
+            }
+        });
+
+        let mut apply = false;
+        if let Some(result) = &self.optimizer_result {
+            let rate = |estimate: &Estimate| {
+                format!(
+                    "{:.1} ± {:.1}",
+                    estimate.rate * 100.0,
+                    estimate.error * 100.0
+                )
+            };
+            ui.label(text::ui_fmt(
+                "optimizer_rates",
+                &[&rate(&result.current), &rate(&result.suggestion.rate)],
+            ));
+            if result.suggestion.statues.is_empty() {
+                ui.label(text::ui("optimizer_no_boost"));
+            } else {
+                let names = result
+                    .suggestion
+                    .statues
+                    .iter()
+                    .map(statue_name)
+                    .collect::<Vec<String>>();
+                ui.label(names.join(", "));
+                apply = ui.button(text::ui("use_suggestion")).clicked();
+            }
+        }
+        if apply {
+            if let Some(result) = &self.optimizer_result {
+                self.statue_config.statues = result.suggestion.statues.clone();
+            }
+        }
+    }
+
+    fn show_statue_presets(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("statue_presets")
+            .num_columns(2)
+            .spacing([5.0, 5.0])
+            .show(ui, |ui| {
+                ui.label(text::ui("preset"));
+                egui::ComboBox::from_id_source("cmb_statue_preset")
+                    .width(150.0)
+                    .selected_text(match self.selected_preset {
+                        Some(index) => self.statue_presets[index].name.as_str(),
+                        None => text::ui("none"),
+                    })
+                    .show_ui(ui, |ui| {
+                        for (index, preset) in self.statue_presets.iter().enumerate() {
+                            if ui
+                                .selectable_label(self.selected_preset == Some(index), &preset.name)
+                                .clicked()
+                            {
+                                self.selected_preset = Some(index);
+                                self.preset_name = preset.name.clone();
+                            }
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(text::ui("name"));
+                egui::TextEdit::singleline(&mut self.preset_name)
+                    .desired_width(150.0)
+                    .show(ui);
+                ui.end_row();
+            });
+
+        let name = self.preset_name.trim().to_string();
+        let selected = self.selected_preset;
+        let name_taken = |presets: &[StatuePreset]| {
+            presets
+                .iter()
+                .enumerate()
+                .any(|(index, preset)| preset.name == name && Some(index) != selected)
+        };
+
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new(text::ui("load")))
+                .clicked()
+            {
+                if let Some(index) = selected {
+                    self.statue_config.statues = self.statue_presets[index].statues.clone();
+                }
+            }
+
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new(text::ui("save")))
+                .on_hover_text(text::ui("save_preset_hint"))
+                .clicked()
+            {
+                let statues = self.statue_config.statues.clone();
+                match self.statue_presets.iter().position(|p| p.name == name) {
+                    Some(index) => {
+                        self.statue_presets[index].statues = statues;
+                        self.selected_preset = Some(index);
+                    }
+                    None => {
+                        self.statue_presets.push(StatuePreset {
+                            name: name.clone(),
+                            statues,
+                        });
+                        self.selected_preset = Some(self.statue_presets.len() - 1);
+                    }
+                }
+            }
+
+            let can_rename =
+                selected.is_some() && !name.is_empty() && !name_taken(&self.statue_presets);
+            if ui
+                .add_enabled(can_rename, egui::Button::new(text::ui("rename")))
+                .clicked()
+            {
+                if let Some(index) = selected {
+                    self.statue_presets[index].name = name.clone();
+                }
+            }
+
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new(text::ui("duplicate")))
+                .clicked()
+            {
+                if let Some(index) = selected {
+                    let mut preset = self.statue_presets[index].clone();
+                    let mut copy = 1;
+                    preset.name = text::ui_fmt("preset_copy", &[&self.statue_presets[index].name]);
+                    while self.statue_presets.iter().any(|p| p.name == preset.name) {
+                        copy += 1;
+                        preset.name = text::ui_fmt(
+                            "preset_copy_n",
+                            &[&self.statue_presets[index].name, &copy],
+                        );
+                    }
+                    self.preset_name = preset.name.clone();
+                    self.statue_presets.push(preset);
+                    self.selected_preset = Some(self.statue_presets.len() - 1);
+                }
+            }
+
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new(text::ui("delete")))
+                .clicked()
+            {
+                if let Some(index) = selected {
+                    self.statue_presets.remove(index);
+                    self.selected_preset = None;
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,170 @@
+use crate::search::{ResultColumn, ResultRow};
+use eframe::egui;
+use egui_extras::{Size, TableBuilder};
+
+const COLUMN_WIDTHS: [f32; 16] = [
+    80.0, 80.0, 100.0, 60.0, 40.0, 40.0, 40.0, 40.0, 40.0, 40.0, 100.0, 70.0, 70.0, 70.0, 100.0,
+    80.0,
+];
+
+/// Sorting and filtering applied to the results table after a search has run.
+#[derive(Default)]
+pub(crate) struct ResultsView {
+    /// Sorted column and whether it is sorted in descending order.
+    sort: Option<(ResultColumn, bool)>,
+    filters: [String; 16],
+    /// Indices into the results, in display order.
+    rows: Vec<usize>,
+    /// Index into the results of the row shown in the details panel.
+    selected: Option<usize>,
+    dirty: bool,
+}
+
+impl ResultsView {
+    /// Marks the view as stale after the results changed.
+    pub(crate) fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    pub(crate) fn clear_filters(&mut self) {
+        self.filters = Default::default();
+        self.dirty = true;
+    }
+
+    pub(crate) fn selected<'a>(&self, results: &'a [ResultRow]) -> Option<&'a ResultRow> {
+        self.selected.and_then(|index| results.get(index))
+    }
+
+    pub(crate) fn clear_selection(&mut self) {
+        self.selected = None;
+    }
+
+    fn rebuild(&mut self, results: &[ResultRow]) {
+        self.rows = (0..results.len())
+            .filter(|&i| {
+                ResultColumn::ALL
+                    .into_iter()
+                    .zip(&self.filters)
+                    .all(|(column, filter)| matches_filter(&results[i], column, filter))
+            })
+            .collect();
+        if let Some((column, descending)) = self.sort {
+            self.rows.sort_by(|&a, &b| {
+                let ordering = results[a].compare(&results[b], column);
+                if descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+        self.dirty = false;
+    }
+
+    pub(crate) fn show(&mut self, ui: &mut egui::Ui, results: &[ResultRow]) {
+        if self.dirty {
+            self.rebuild(results);
+        }
+
+        let mut changed = false;
+        let mut table =
+            TableBuilder::new(ui)
+                .striped(true)
+                .cell_layout(egui::Layout::centered_and_justified(
+                    egui::Direction::LeftToRight,
+                ));
+        for width in COLUMN_WIDTHS {
+            table = table.column(Size::initial(width).at_least(width));
+        }
+        table
+            .resizable(true)
+            .header(45.0, |mut header| {
+                for (column, filter) in ResultColumn::ALL.into_iter().zip(&mut self.filters) {
+                    header.col(|ui| {
+                        ui.vertical_centered_justified(|ui| {
+                            let label = match self.sort {
+                                Some((sorted, descending)) if sorted == column => format!(
+                                    "{} {}",
+                                    column.header(),
+                                    if descending { "▼" } else { "▲" }
+                                ),
+                                _ => column.header().to_string(),
+                            };
+                            let heading = egui::RichText::new(label).heading();
+                            if ui.add(egui::Button::new(heading).frame(false)).clicked() {
+                                self.sort = match self.sort {
+                                    Some((sorted, false)) if sorted == column => {
+                                        Some((column, true))
+                                    }
+                                    _ => Some((column, false)),
+                                };
+                                changed = true;
+                            }
+                            if ui.text_edit_singleline(filter).changed() {
+                                changed = true;
+                            }
+                        });
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(18.0, self.rows.len(), |index, mut row| {
+                    let result_index = self.rows[index];
+                    let result = &results[result_index];
+                    let selected = self.selected == Some(result_index);
+                    for column in ResultColumn::ALL {
+                        row.col(|ui| {
+                            if ui.selectable_label(selected, result.text(column)).clicked() {
+                                self.selected = Some(result_index);
+                            }
+                        });
+                    }
+                });
+            });
+
+        if changed {
+            self.rebuild(results);
+        }
+    }
+}
+
+/// Numeric columns accept `<`, `<=`, `>`, `>=` or `=` followed by a number and match exactly
+/// without an operator. Every other column matches case-insensitive substrings.
+fn matches_filter(row: &ResultRow, column: ResultColumn, filter: &str) -> bool {
+    let filter = filter.trim();
+    if filter.is_empty() {
+        return true;
+    }
+
+    let decimal = matches!(
+        column,
+        ResultColumn::Advance
+            | ResultColumn::Hp
+            | ResultColumn::Atk
+            | ResultColumn::Def
+            | ResultColumn::Spa
+            | ResultColumn::Spd
+            | ResultColumn::Spe
+    );
+    if decimal {
+        if let Some(value) = row.number(column) {
+            let (operator, number) = ["<=", ">=", "<", ">", "="]
+                .into_iter()
+                .find_map(|op| filter.strip_prefix(op).map(|rest| (op, rest)))
+                .unwrap_or(("=", filter));
+            if let Ok(number) = number.trim().parse::<u32>() {
+                return match operator {
+                    "<=" => value <= number,
+                    ">=" => value >= number,
+                    "<" => value < number,
+                    ">" => value > number,
+                    _ => value == number,
+                };
+            }
+        }
+    }
+
+    row.text(column)
+        .to_lowercase()
+        .contains(&filter.to_lowercase())
+}
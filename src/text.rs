@@ -0,0 +1,240 @@
+use crate::{
+    load_string_list, ABILITIES_EN, CHARACTERISTICS_EN, ITEMS_EN, MOVES_EN, NATURES_EN, SPECIES_EN,
+    TYPES_EN,
+};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Generated by build.rs from the files in `resources/text`.
+include!(concat!(env!("OUT_DIR"), "/bundled_text.rs"));
+
+/// Languages the game and UI text can be shown in.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Japanese,
+    French,
+    German,
+    Spanish,
+    Italian,
+    Korean,
+    ChineseSimplified,
+    ChineseTraditional,
+}
+
+impl Language {
+    pub const ALL: [Language; 9] = [
+        Language::English,
+        Language::Japanese,
+        Language::French,
+        Language::German,
+        Language::Spanish,
+        Language::Italian,
+        Language::Korean,
+        Language::ChineseSimplified,
+        Language::ChineseTraditional,
+    ];
+
+    /// Code used in the resource file names, e.g. `species_fr.txt`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Japanese => "ja",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Spanish => "es",
+            Language::Italian => "it",
+            Language::Korean => "ko",
+            Language::ChineseSimplified => "zh-Hans",
+            Language::ChineseTraditional => "zh-Hant",
+        }
+    }
+
+    pub fn get_str(self) -> &'static str {
+        ui(match self {
+            Language::English => "language_english",
+            Language::Japanese => "language_japanese",
+            Language::French => "language_french",
+            Language::German => "language_german",
+            Language::Spanish => "language_spanish",
+            Language::Italian => "language_italian",
+            Language::Korean => "language_korean",
+            Language::ChineseSimplified => "language_chinese_simplified",
+            Language::ChineseTraditional => "language_chinese_traditional",
+        })
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL
+            .into_iter()
+            .find(|language| language.code() == code)
+    }
+}
+
+/// Bundled game text list of `kind` for `language`.
+///
+/// Lists hold one entry per line in game id order. They live at
+/// `resources/text/other/<code>/<kind>_<code>.txt`, except items at
+/// `resources/text/items/items_<code>.txt`. Every file there is bundled by build.rs, so a
+/// language is added by dropping its files into that layout. Lists that are missing, and
+/// entries left empty, fall back to English.
+fn game_text(kind: &str, language: Language) -> Option<&'static str> {
+    let code = language.code();
+    if kind == "items" {
+        bundled_text(&format!("items/items_{}.txt", code))
+    } else {
+        bundled_text(&format!("other/{}/{}_{}.txt", code, kind, code))
+    }
+}
+
+/// Bundled UI catalogue for `language`, `id=Text` lines at `resources/text/ui/ui_<code>.txt`.
+/// Like game text, missing catalogues and ids fall back to English.
+fn ui_catalogue(language: Language) -> Option<&'static str> {
+    bundled_text(&format!("ui/ui_{}.txt", language.code()))
+}
+
+/// English game text list of `kind`, see [`game_text`].
+pub(crate) fn english_list(kind: &str) -> Vec<&'static str> {
+    game_text(kind, Language::English)
+        .map(load_string_list)
+        .unwrap_or_default()
+}
+
+struct Tables {
+    species: Vec<&'static str>,
+    abilities: Vec<&'static str>,
+    natures: Vec<&'static str>,
+    moves: Vec<&'static str>,
+    items: Vec<&'static str>,
+    types: Vec<&'static str>,
+    characteristics: Vec<&'static str>,
+    ui: HashMap<&'static str, &'static str>,
+}
+
+/// Parses a UI catalogue, skipping blank lines, `#` comments and empty entries.
+fn load_catalogue(raw: &'static str) -> HashMap<&'static str, &'static str> {
+    load_string_list(raw)
+        .into_iter()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(id, text)| (id.trim(), text))
+        .filter(|(_, text)| !text.is_empty())
+        .collect()
+}
+
+impl Tables {
+    fn load(language: Language) -> Self {
+        let load = |kind| {
+            game_text(kind, language)
+                .map(load_string_list)
+                .unwrap_or_default()
+        };
+        Self {
+            species: load("species"),
+            abilities: load("abilities"),
+            natures: load("natures"),
+            moves: load("moves"),
+            items: load("items"),
+            types: load("types"),
+            characteristics: load("characteristics"),
+            ui: ui_catalogue(language)
+                .map(load_catalogue)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref TABLES: Vec<Tables> = Language::ALL.into_iter().map(Tables::load).collect();
+}
+
+static LANGUAGE: AtomicUsize = AtomicUsize::new(0);
+
+/// Switches the language game and UI text are looked up in.
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as usize, Ordering::Relaxed);
+}
+
+fn lookup(
+    table: impl Fn(&'static Tables) -> &'static [&'static str],
+    english: &'static [&'static str],
+    id: usize,
+) -> &'static str {
+    table(&TABLES[LANGUAGE.load(Ordering::Relaxed)])
+        .get(id)
+        .copied()
+        .filter(|text| !text.is_empty())
+        .unwrap_or_else(|| english.get(id).copied().unwrap_or_default())
+}
+
+pub fn species(id: usize) -> &'static str {
+    lookup(|tables| &tables.species, &SPECIES_EN, id)
+}
+
+pub fn ability(id: usize) -> &'static str {
+    lookup(|tables| &tables.abilities, &ABILITIES_EN, id)
+}
+
+pub fn nature(id: usize) -> &'static str {
+    lookup(|tables| &tables.natures, &NATURES_EN, id)
+}
+
+pub fn move_name(id: usize) -> &'static str {
+    lookup(|tables| &tables.moves, &MOVES_EN, id)
+}
+
+pub fn item(id: usize) -> &'static str {
+    lookup(|tables| &tables.items, &ITEMS_EN, id)
+}
+
+/// Name of the type at `id`, in `types_<code>.txt` order from Normal to Fairy.
+pub fn type_name(id: usize) -> &'static str {
+    lookup(|tables| &tables.types, &TYPES_EN, id)
+}
+
+/// Characteristic for the highest IV, by stat in HP, Atk, Def, Spe, SpA, SpD order and the
+/// IV mod 5. `characteristics_<code>.txt` lists the five of each stat in turn.
+pub fn characteristic(stat: usize, iv_mod: usize) -> &'static str {
+    lookup(
+        |tables| &tables.characteristics,
+        &CHARACTERISTICS_EN,
+        stat * 5 + iv_mod,
+    )
+}
+
+/// UI text for `id`, falling back to English and then to the id itself.
+pub fn ui(id: &'static str) -> &'static str {
+    let text = |language: usize| TABLES[language].ui.get(id).copied();
+    text(LANGUAGE.load(Ordering::Relaxed))
+        .or_else(|| text(Language::English as usize))
+        .unwrap_or(id)
+}
+
+/// UI text for `id` with each `{}` replaced by the next argument.
+pub fn ui_fmt(id: &'static str, args: &[&dyn Display]) -> String {
+    let mut parts = ui(id).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        if let Some(arg) = args.get(i) {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_text_is_found_by_code() {
+        assert!(ui_catalogue(Language::English).is_some());
+        assert_eq!(english_list("types").len(), 18);
+        assert_eq!(english_list("characteristics").len(), 30);
+        assert_eq!(english_list("natures").len(), 25);
+    }
+}
@@ -0,0 +1,396 @@
+use crate::search::{self, ResultRow, SearchRequest};
+use crate::{text, BDSPUgGeneratorUI};
+use eframe::egui;
+use lazy_static::lazy_static;
+
+/// Number of advances of upcoming spawns shown in the tracker.
+const UPCOMING_ADVANCES: u32 = 10;
+
+pub(crate) const DEFAULT_RATE: f32 = 60.0;
+
+/// Steps the game's xorshift128 generator once.
+///
+/// The generator crate's `XorShift` doesn't expose its state, so the tracker keeps its own
+/// copy and hands it to searches through `XorShift::from_state`.
+fn next(state: &mut [u32; 4]) -> u32 {
+    let mut t = state[0];
+    let s = state[3];
+    t ^= t << 11;
+    t ^= t >> 8;
+    state[0] = state[1];
+    state[1] = state[2];
+    state[2] = state[3];
+    state[3] = t ^ s ^ (s >> 19);
+    state[3]
+}
+
+fn state_to_bits(state: [u32; 4]) -> u128 {
+    state
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (i, &word)| bits | (word as u128) << (32 * i))
+}
+
+fn bits_to_state(bits: u128) -> [u32; 4] {
+    [0, 1, 2, 3].map(|i| (bits >> (32 * i)) as u32)
+}
+
+/// Applies a linear map over GF(2), given as the image of each of the 128 state bits.
+fn apply(matrix: &[u128; 128], bits: u128) -> u128 {
+    (0..128)
+        .filter(|&i| bits >> i & 1 == 1)
+        .fold(0, |image, i| image ^ matrix[i])
+}
+
+lazy_static! {
+    /// `next` is linear over GF(2), so stepping 2^k times is a fixed matrix. Entry `k` holds it.
+    static ref JUMP_MATRICES: Vec<[u128; 128]> = {
+        let mut step = [0; 128];
+        for (i, image) in step.iter_mut().enumerate() {
+            let mut state = bits_to_state(1 << i);
+            next(&mut state);
+            *image = state_to_bits(state);
+        }
+        let mut matrices = vec![step];
+        for _ in 1..32 {
+            let last = matrices[matrices.len() - 1];
+            matrices.push(last.map(|image| apply(&last, image)));
+        }
+        matrices
+    };
+}
+
+/// Steps the generator `count` times in at most 32 matrix applications, like
+/// `XorShift::jump` does for searches.
+fn jump(state: &mut [u32; 4], count: u32) {
+    let mut bits = state_to_bits(*state);
+    for (k, matrix) in JUMP_MATRICES.iter().enumerate() {
+        if count >> k & 1 == 1 {
+            bits = apply(matrix, bits);
+        }
+    }
+    *state = bits_to_state(bits);
+}
+
+/// Whether the upcoming spawns for `a` and `b` are the same, the state aside.
+fn same_settings(a: &SearchRequest, b: &SearchRequest) -> bool {
+    a.delay == b.delay
+        && a.version == b.version
+        && a.story_flag == b.story_flag
+        && a.room == b.room
+        && a.diglett_mode == b.diglett_mode
+        && a.statues.len() == b.statues.len()
+        && a.statues
+            .iter()
+            .zip(&b.statues)
+            .all(|(a, b)| a.mons_id == b.mons_id && a.rarity == b.rarity)
+}
+
+/// A generator state followed along while playing.
+pub(crate) struct Tracker {
+    state: [u32; 4],
+    advance: u32,
+    pub(crate) step: u32,
+    pub(crate) target: u32,
+    /// Result marked as the target, shown while its advance is still the target.
+    target_row: Option<ResultRow>,
+    /// Estimated advances per second while playing, used by the timer.
+    pub(crate) rate: f32,
+    /// Time and advance the timer was started at.
+    timer: Option<(f64, u32)>,
+    upcoming: Vec<ResultRow>,
+    /// Settings `upcoming` was searched with.
+    upcoming_settings: Option<SearchRequest>,
+    dirty: bool,
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self {
+            state: [0; 4],
+            advance: 0,
+            step: 1,
+            target: 0,
+            target_row: None,
+            rate: DEFAULT_RATE,
+            timer: None,
+            upcoming: vec![],
+            upcoming_settings: None,
+            dirty: true,
+        }
+    }
+}
+
+impl Tracker {
+    pub(crate) fn reset(&mut self, state: [u32; 4]) {
+        self.state = state;
+        self.advance = 0;
+        self.dirty = true;
+    }
+
+    pub(crate) fn advance(&mut self, count: u32) {
+        jump(&mut self.state, count);
+        self.advance += count;
+        self.dirty = true;
+    }
+
+    pub(crate) fn advance_to(&mut self, target: u32) {
+        self.advance(target.saturating_sub(self.advance));
+    }
+
+    pub(crate) fn set_target(&mut self, row: ResultRow) {
+        self.target = row.advance;
+        self.target_row = Some(row);
+    }
+
+    pub(crate) fn start_timer(&mut self, now: f64) {
+        self.timer = Some((now, self.advance));
+    }
+
+    /// Stops the timer and moves the state to the advance it estimated.
+    pub(crate) fn stop_timer(&mut self, now: f64) {
+        let advance = self.estimated_advance(now);
+        self.timer = None;
+        self.advance_to(advance);
+    }
+
+    pub(crate) fn timer_running(&self) -> bool {
+        self.timer.is_some()
+    }
+
+    /// The current advance, or while the timer runs, the advance it has counted up to.
+    pub(crate) fn estimated_advance(&self, now: f64) -> u32 {
+        match self.timer {
+            Some((start, advance)) => advance + ((now - start).max(0.0) * self.rate as f64) as u32,
+            None => self.advance,
+        }
+    }
+
+    pub(crate) fn state(&self) -> [u32; 4] {
+        self.state
+    }
+
+    /// Marks the upcoming spawns as stale after the search settings changed.
+    pub(crate) fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Spawns for the next few advances, numbered from the start of tracking. Searched again
+    /// when the state moved or the main window's settings changed.
+    fn upcoming(&mut self, settings: &SearchRequest) -> &[ResultRow] {
+        let changed = self
+            .upcoming_settings
+            .as_ref()
+            .map(|upcoming_settings| !same_settings(upcoming_settings, settings))
+            .unwrap_or(true);
+        if self.dirty || changed {
+            let request = SearchRequest {
+                state: self.state,
+                min_advances: 0,
+                max_advances: UPCOMING_ADVANCES,
+                filter: Default::default(),
+                ..settings.clone()
+            };
+            self.upcoming = search::search(&request).rows;
+            for row in &mut self.upcoming {
+                row.advance += self.advance;
+            }
+            self.upcoming_settings = Some(settings.clone());
+            self.dirty = false;
+        }
+        &self.upcoming
+    }
+}
+
+impl BDSPUgGeneratorUI {
+    pub(crate) fn show_tracker_window(&mut self, ctx: &egui::Context) {
+        let settings = SearchRequest {
+            delay: self.delay,
+            version: self.version,
+            story_flag: self.story_flag,
+            room: self.room,
+            diglett_mode: self.diglett_mode,
+            statues: self.statue_config.statues.clone(),
+            ..Default::default()
+        };
+
+        egui::Window::new(text::ui("tracker"))
+            .id(egui::Id::new("tracker_window"))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(text::ui("start_from_seeds"))
+                        .on_hover_text(text::ui("start_from_seeds_hint"))
+                        .clicked()
+                    {
+                        match search::parse_state(&self.s0, &self.s1, &self.s2, &self.s3) {
+                            Ok(state) => {
+                                self.tracker.reset(state);
+                                self.error.clear();
+                            }
+                            Err(e) => self.error = e.to_string(),
+                        }
+                    }
+                    if ui.button(text::ui("refresh")).clicked() {
+                        self.tracker.invalidate();
+                    }
+                    if ui.button(text::ui("close")).clicked() {
+                        self.show_tracker = false;
+                    }
+                });
+                ui.add_space(5.0);
+
+                egui::Grid::new("tracker_controls")
+                    .num_columns(3)
+                    .spacing([5.0, 5.0])
+                    .show(ui, |ui| {
+                        ui.label(text::ui("step"));
+                        ui.add(
+                            egui::DragValue::new(&mut self.tracker.step).clamp_range(1..=1_000_000),
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("+1").clicked() {
+                                self.tracker.advance(1);
+                            }
+                            if ui.button(format!("+{}", self.tracker.step)).clicked() {
+                                let step = self.tracker.step;
+                                self.tracker.advance(step);
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label(text::ui("target"));
+                        ui.add(egui::DragValue::new(&mut self.tracker.target));
+                        if ui.button(text::ui("go_to_target")).clicked() {
+                            let target = self.tracker.target;
+                            self.tracker.advance_to(target);
+                        }
+                        ui.end_row();
+                    });
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(text::ui("rate"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.tracker.rate)
+                            .clamp_range(0.1..=10000.0)
+                            .suffix(format!(" {}", text::ui("advances_per_second"))),
+                    )
+                    .on_hover_text(text::ui("rate_hint"));
+                    let now = ui.input().time;
+                    if self.tracker.timer_running() {
+                        if ui.button(text::ui("stop_timer")).clicked() {
+                            self.tracker.stop_timer(now);
+                        }
+                    } else if ui.button(text::ui("start_timer")).clicked() {
+                        self.tracker.start_timer(now);
+                    }
+                });
+
+                let now = ui.input().time;
+                if self.tracker.timer_running() {
+                    ctx.request_repaint();
+                }
+                let advance = self.tracker.estimated_advance(now);
+                let target = self.tracker.target;
+                if let Some(row) = self.tracker.target_row.filter(|row| row.advance == target) {
+                    ui.label(text::ui_fmt(
+                        if row.shiny {
+                            "tracker_target_shiny"
+                        } else {
+                            "tracker_target"
+                        },
+                        &[&row.species_str(), &row.advance],
+                    ));
+                }
+                ui.label(if advance < target {
+                    let seconds = ((target - advance) as f32 / self.tracker.rate) as u32;
+                    text::ui_fmt(
+                        "tracker_before_target",
+                        &[
+                            &advance,
+                            &target,
+                            &(target - advance),
+                            &format!("{}:{:02}", seconds / 60, seconds % 60),
+                        ],
+                    )
+                } else if advance == target {
+                    text::ui_fmt("tracker_at_target", &[&advance])
+                } else {
+                    text::ui_fmt(
+                        "tracker_past_target",
+                        &[&advance, &(advance - target), &target],
+                    )
+                });
+                if self.delay > 0 {
+                    ui.label(text::ui_fmt("tracker_delay", &[&target, &self.delay]));
+                }
+                let state = self.tracker.state();
+                egui::Grid::new("tracker_state")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for (i, seed) in state.iter().enumerate() {
+                            ui.label(format!("s{}", i));
+                            ui.monospace(format!("{:08X}", seed));
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(5.0);
+
+                ui.heading(text::ui("upcoming"));
+                egui::Grid::new("tracker_upcoming")
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong(text::ui("column_advance"));
+                        ui.strong(text::ui("column_species"));
+                        ui.strong(text::ui("column_shiny"));
+                        ui.strong(text::ui("ivs"));
+                        ui.strong(text::ui("column_nature"));
+                        ui.end_row();
+                        for row in self.tracker.upcoming(&settings) {
+                            ui.label(row.advance.to_string());
+                            ui.label(row.species_str());
+                            ui.label(row.shiny_str());
+                            ui.label(
+                                row.ivs
+                                    .iter()
+                                    .map(|iv| iv.to_string())
+                                    .collect::<Vec<String>>()
+                                    .join("/"),
+                            );
+                            ui.label(row.nature_str());
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jump_matches_stepping() {
+        let start = [0x1234ABCD, 0x00000001, 0xDEADBEEF, 0x0F0F0F0F];
+        let mut stepped = start;
+        for count in 0..=1000 {
+            let mut jumped = start;
+            jump(&mut jumped, count);
+            assert_eq!(jumped, stepped, "after {} advances", count);
+            next(&mut stepped);
+        }
+    }
+
+    #[test]
+    fn jumps_add_up() {
+        let start = [0x1234ABCD, 0x00000001, 0xDEADBEEF, 0x0F0F0F0F];
+        let mut once = start;
+        jump(&mut once, 3_000_000_000);
+        let mut twice = start;
+        jump(&mut twice, 1_234_567_890);
+        jump(&mut twice, 3_000_000_000 - 1_234_567_890);
+        assert_eq!(once, twice);
+    }
+}